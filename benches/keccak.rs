@@ -3,7 +3,9 @@
 extern crate test;
 
 use test::Bencher;
-use tiny_keccak::{keccakf, Keccak, Hasher};
+use tiny_keccak::{keccakf, Hasher, Keccak};
+#[cfg(feature = "sponge")]
+use tiny_keccak::{KeccakF, Sponge};
 
 #[bench]
 fn bench_keccak_256_input_4096_bytes(b: &mut Bencher) {
@@ -18,6 +20,7 @@ fn bench_keccak_256_input_4096_bytes(b: &mut Bencher) {
     });
 }
 
+// run with and without the `small` feature to compare the unrolled and the looped permutation
 #[bench]
 fn keccakf_u64(b: &mut Bencher) {
     const WORDS: usize = 25;
@@ -41,3 +44,58 @@ fn bench_keccak256(b: &mut Bencher) {
         keccak.finalize(&mut res);
     });
 }
+
+#[cfg(feature = "lane_complementing")]
+#[bench]
+fn keccakf_complementing_u64(b: &mut Bencher) {
+    const WORDS: usize = 25;
+    b.bytes = (WORDS * 8) as u64;
+
+    b.iter(|| {
+        let mut data = [0u64; WORDS];
+        tiny_keccak::keccakf_complementing(&mut data);
+    });
+}
+
+// absorbing and squeezing 4096 bytes at the rates of SHA3-512, SHA3-384, SHA3-256, SHA3-224 and
+// SHAKE128, to measure the cost of loading and storing the blocks next to the permutation
+#[cfg(feature = "sponge")]
+macro_rules! bench_rate {
+    ($absorb: ident, $squeeze: ident, $rate: expr) => {
+        #[bench]
+        fn $absorb(b: &mut Bencher) {
+            let data = [254u8; 4096];
+            b.bytes = data.len() as u64;
+
+            b.iter(|| {
+                let mut sponge = Sponge::<KeccakF>::new($rate, 0x01);
+                sponge.absorb(test::black_box(&data));
+                sponge.pad();
+                sponge
+            });
+        }
+
+        #[bench]
+        fn $squeeze(b: &mut Bencher) {
+            let mut data = [0u8; 4096];
+            b.bytes = data.len() as u64;
+
+            b.iter(|| {
+                let mut sponge = Sponge::<KeccakF>::new($rate, 0x01);
+                sponge.squeeze(&mut data);
+                test::black_box(&data);
+            });
+        }
+    };
+}
+
+#[cfg(feature = "sponge")]
+bench_rate!(absorb_rate_72, squeeze_rate_72, 72);
+#[cfg(feature = "sponge")]
+bench_rate!(absorb_rate_104, squeeze_rate_104, 104);
+#[cfg(feature = "sponge")]
+bench_rate!(absorb_rate_136, squeeze_rate_136, 136);
+#[cfg(feature = "sponge")]
+bench_rate!(absorb_rate_144, squeeze_rate_144, 144);
+#[cfg(feature = "sponge")]
+bench_rate!(absorb_rate_168, squeeze_rate_168, 168);
@@ -1,19 +1,33 @@
 #[cfg(not(any(
+    feature = "duplex",
+    feature = "farfalle",
     feature = "keccak",
+    feature = "keccakf200",
+    feature = "keccakf400",
+    feature = "keccakf800",
+    feature = "lane_complementing",
+    feature = "kravatte",
     feature = "shake",
     feature = "sha3",
     feature = "cshake",
     feature = "kmac",
+    feature = "masked",
     feature = "tuple_hash",
     feature = "parallel_hash",
     feature = "k12",
+    feature = "m14",
+    feature = "turbo_shake",
     feature = "fips202",
-    feature = "sp800"
+    feature = "sp800",
+    feature = "sponge",
+    feature = "sponge_wrap",
+    feature = "strobe",
+    feature = "transcript"
 )))]
 compile_error!(
     "You need to specify at least one hash function you intend to use. \
     Available options:\n\
-    keccak, shake, sha3, cshake, kmac, tuple_hash, parallel_hash, k12, fips202, sp800\n\
+    duplex, farfalle, keccak, keccakf200, keccakf400, keccakf800, lane_complementing, kravatte, shake, sha3, cshake, kmac, masked, tuple_hash, parallel_hash, k12, m14, turbo_shake, fips202, sp800, sponge, sponge_wrap, strobe, transcript\n\
     e.g.\n\
     tiny-keccak = { version = \"2.0.0\", features = [\"sha3\"] }"
 );
@@ -0,0 +1,17 @@
+use tiny_keccak::{Hasher, KangarooTwelve};
+
+fn main() {
+    // a customization string spanning several chunks, whose leaves are hashed in batches
+    let custom_string: Vec<u8> = (0..68921).map(|j| (j % 251) as u8).collect();
+    let mut kangaroo = KangarooTwelve::new(&custom_string);
+    let mut output = [0; 32];
+    let expected = b"\
+        \x75\xd2\xf8\x6a\x2e\x64\x45\x66\x72\x6b\x4f\xbc\xfc\x56\x57\xb9\
+        \xdb\xcf\x07\x0c\x7b\x0d\xca\x06\x45\x0a\xb2\x91\xd7\x44\x3b\xcf\
+    ";
+
+    kangaroo.update(&[0xff; 7]);
+    kangaroo.finalize(&mut output);
+
+    assert_eq!(expected, &output);
+}
@@ -2,7 +2,9 @@
 //!
 //! [`SP800-185`]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-185.pdf
 
-use crate::{bits_to_rate, keccakf::KeccakF, left_encode, Hasher, KeccakState, Xof};
+#[cfg(all(feature = "fault_detection", feature = "kmac"))]
+use crate::FaultDetected;
+use crate::{bits_to_rate, left_encode, Hasher, KeccakFChecked, KeccakState, Xof};
 
 /// The `cSHAKE` extendable-output functions defined in [`SP800-185`].
 ///
@@ -16,7 +18,7 @@ use crate::{bits_to_rate, keccakf::KeccakF, left_encode, Hasher, KeccakState, Xo
 /// [`SP800-185`]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-185.pdf
 #[derive(Clone)]
 pub struct CShake {
-    state: KeccakState<KeccakF>,
+    state: KeccakState<KeccakFChecked>,
 }
 
 impl CShake {
@@ -55,9 +57,15 @@ impl CShake {
         CShake { state }
     }
 
+    #[cfg(feature = "kmac")]
     pub(crate) fn fill_block(&mut self) {
         self.state.fill_block();
     }
+
+    #[cfg(all(feature = "fault_detection", feature = "kmac"))]
+    pub(crate) fn try_finalize(self, output: &mut [u8]) -> Result<(), FaultDetected> {
+        self.state.try_finalize(output)
+    }
 }
 
 impl Hasher for CShake {
@@ -0,0 +1,143 @@
+//! The duplex construction defined [`here`].
+//!
+//! [`here`]: https://keccak.team/files/SpongeDuplex.pdf
+
+use crate::{Buffer, Permutation, WORDS};
+
+/// The duplex construction `Duplex[f, pad, r]` over an arbitrary [`Permutation`].
+///
+/// Unlike [`Sponge`], every call to [`duplexing`] pads its own input block, applies the
+/// permutation and returns a part of the new state, so input and output can be interleaved
+/// at block granularity. Each input block may hold up to `rate - 1` bytes (at most `r - 2`
+/// bits, leaving room for the padding), and each output block up to `rate` bytes.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["duplex"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{keccak_p, Duplex, Permutation};
+/// struct KeccakP12;
+///
+/// impl Permutation for KeccakP12 {
+///     type Lane = u64;
+///
+///     fn execute(state: &mut [u64; 25]) {
+///         keccak_p(state, 12);
+///     }
+/// }
+///
+/// let mut duplex = Duplex::<KeccakP12>::new(166, 0x01);
+/// let mut output = [0u8; 32];
+/// duplex.duplexing(b"hello", &mut output);
+/// duplex.duplexing(b"world", &mut output);
+/// ```
+///
+/// [`Permutation`]: trait.Permutation.html
+/// [`Sponge`]: struct.Sponge.html
+/// [`duplexing`]: struct.Duplex.html#method.duplexing
+pub struct Duplex<P: Permutation> {
+    buffer: Buffer<P::Lane>,
+    rate: usize,
+    delim: u8,
+}
+
+impl<P: Permutation> Clone for Duplex<P> {
+    fn clone(&self) -> Self {
+        Duplex {
+            buffer: self.buffer.clone(),
+            rate: self.rate,
+            delim: self.delim,
+        }
+    }
+}
+
+impl<P: Permutation> Duplex<P> {
+    /// Creates new [`Duplex`] with a rate of `rate` bytes and the domain separation `suffix`.
+    ///
+    /// The `suffix` byte holds the frame bits followed by the first bit of the padding; `0x01`
+    /// is plain `pad10*1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is less than `2` or greater than the size of the state, or if `suffix`
+    /// is `0`, which holds no padding bit.
+    ///
+    /// [`Duplex`]: struct.Duplex.html
+    pub fn new(rate: usize, suffix: u8) -> Duplex<P> {
+        assert!(rate >= 2, "rate cannot be less than 2");
+        assert!(suffix != 0, "suffix cannot be equal 0");
+        assert!(
+            rate <= WORDS * core::mem::size_of::<P::Lane>(),
+            "rate cannot be greater than the state size"
+        );
+        Duplex {
+            buffer: Buffer::default(),
+            rate,
+            delim: suffix,
+        }
+    }
+
+    /// Returns the rate of the duplex object in bytes.
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// Returns the capacity of the duplex object in bytes.
+    pub fn capacity(&self) -> usize {
+        WORDS * core::mem::size_of::<P::Lane>() - self.rate
+    }
+
+    /// Returns the maximum length of an input block in bytes.
+    pub fn max_input_len(&self) -> usize {
+        self.rate - 1
+    }
+
+    /// Absorbs one padded input block, applies the permutation and squeezes one output block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is longer than [`max_input_len`] or `output` is longer than
+    /// [`rate`].
+    ///
+    /// [`max_input_len`]: struct.Duplex.html#method.max_input_len
+    /// [`rate`]: struct.Duplex.html#method.rate
+    pub fn duplexing(&mut self, input: &[u8], output: &mut [u8]) {
+        self.duplexing_with_suffix(input, self.delim, output);
+    }
+
+    /// Same as [`duplexing`], but pads the input block with `suffix` instead of the suffix
+    /// the [`Duplex`] was created with. Useful for modes which frame each block with its own
+    /// bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is longer than [`max_input_len`], `output` is longer than [`rate`] or
+    /// `suffix` is `0`.
+    ///
+    /// [`duplexing`]: struct.Duplex.html#method.duplexing
+    /// [`Duplex`]: struct.Duplex.html
+    /// [`max_input_len`]: struct.Duplex.html#method.max_input_len
+    /// [`rate`]: struct.Duplex.html#method.rate
+    pub fn duplexing_with_suffix(&mut self, input: &[u8], suffix: u8, output: &mut [u8]) {
+        assert!(suffix != 0, "suffix cannot be equal 0");
+        assert!(
+            input.len() < self.rate,
+            "input block cannot be longer than rate - 1"
+        );
+        assert!(
+            output.len() <= self.rate,
+            "output block cannot be longer than rate"
+        );
+
+        self.buffer.xorin(input, 0, input.len());
+        self.buffer.pad(input.len(), suffix, self.rate);
+        P::execute(self.buffer.words());
+        self.buffer.setout(output, 0, output.len());
+    }
+}
@@ -0,0 +1,213 @@
+//! The Farfalle construction defined [`here`].
+//!
+//! [`here`]: https://keccak.team/files/Farfalle.pdf
+
+use crate::{Buffer, Permutation, WORDS};
+
+const BLOCK_LEN: usize = WORDS * 8;
+
+/// The rolling functions of a [`Farfalle`] construction.
+///
+/// They act on the 25 `u64` lanes of a `keccak-p[1600]` state, which is the only width
+/// [`Farfalle`] supports.
+///
+/// [`Farfalle`]: struct.Farfalle.html
+pub trait Rolling {
+    /// Rolls the input mask, `roll_c`, between the input blocks.
+    fn roll_c(state: &mut [u64; WORDS]);
+
+    /// Rolls the output state, `roll_e`, between the output blocks.
+    fn roll_e(state: &mut [u64; WORDS]);
+}
+
+/// The Farfalle construction over a [`Permutation`] of `u64` lanes and [`Rolling`] functions.
+///
+/// [`Farfalle`] is a keyed deck function: it compresses a sequence of strings into an
+/// accumulator, each block masked with the rolled key, and expands the accumulator into an
+/// output of any length. The same permutation is used for the key derivation, the
+/// compression and the expansion. Since the accumulator is never consumed, more strings can
+/// be appended after expanding, and the output then covers the whole sequence.
+///
+/// Only permutations of the 1600-bit state, whose `Lane` is `u64`, are supported.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["farfalle"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{keccak_p, Farfalle, Permutation, Rolling};
+/// struct KeccakP6;
+///
+/// impl Permutation for KeccakP6 {
+///     type Lane = u64;
+///
+///     fn execute(state: &mut [u64; 25]) {
+///         keccak_p(state, 6);
+///     }
+/// }
+///
+/// struct Rotate;
+///
+/// impl Rolling for Rotate {
+///     fn roll_c(state: &mut [u64; 25]) {
+///         state[20..].rotate_left(1);
+///         state[24] ^= state[0].rotate_left(7);
+///     }
+///
+///     fn roll_e(state: &mut [u64; 25]) {
+///         state[15..].rotate_left(1);
+///         state[24] ^= state[0].rotate_left(18);
+///     }
+/// }
+///
+/// let mut farfalle = Farfalle::<KeccakP6, Rotate>::new(b"key");
+/// let mut output = [0u8; 32];
+/// farfalle.update(b"hello");
+/// farfalle.update(b" world");
+/// farfalle.end_string();
+/// farfalle.expand(0, &mut output);
+/// ```
+///
+/// [`Permutation`]: trait.Permutation.html
+/// [`Rolling`]: trait.Rolling.html
+/// [`Farfalle`]: struct.Farfalle.html
+pub struct Farfalle<P: Permutation<Lane = u64>, R: Rolling> {
+    mask: [u64; WORDS],
+    accumulator: [u64; WORDS],
+    block: Buffer,
+    offset: usize,
+    _permutation: core::marker::PhantomData<(P, R)>,
+}
+
+impl<P: Permutation<Lane = u64>, R: Rolling> Clone for Farfalle<P, R> {
+    fn clone(&self) -> Self {
+        Farfalle {
+            mask: self.mask,
+            accumulator: self.accumulator,
+            block: self.block.clone(),
+            offset: self.offset,
+            _permutation: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<P: Permutation<Lane = u64>, R: Rolling> Farfalle<P, R> {
+    /// Creates new [`Farfalle`] instance keyed with `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not shorter than the state.
+    ///
+    /// [`Farfalle`]: struct.Farfalle.html
+    pub fn new(key: &[u8]) -> Farfalle<P, R> {
+        assert!(key.len() < BLOCK_LEN, "key must be shorter than the state");
+        let mut mask = Buffer::default();
+        mask.xorin(key, 0, key.len());
+        mask.xorin(&[0x01], key.len(), 1);
+        P::execute(mask.words());
+
+        Farfalle {
+            mask: mask.0,
+            accumulator: [0; WORDS],
+            block: Buffer::default(),
+            offset: 0,
+            _permutation: core::marker::PhantomData,
+        }
+    }
+
+    /// Appends `input` to the string currently being compressed.
+    pub fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            if self.offset == BLOCK_LEN {
+                self.compress();
+            }
+
+            let len = core::cmp::min(BLOCK_LEN - self.offset, input.len());
+            self.block.xorin(input, self.offset, len);
+            self.offset += len;
+            input = &input[len..];
+        }
+    }
+
+    /// Ends the string currently being compressed with the simple `pad10*` padding.
+    pub fn end_string(&mut self) {
+        self.end_string_with_suffix(0x01);
+    }
+
+    /// Ends the string currently being compressed with the domain separation `suffix`.
+    ///
+    /// The `suffix` byte holds the trailing bits of the string followed by the first bit of the
+    /// padding; `0x01` is plain `pad10*`.
+    pub fn end_string_with_suffix(&mut self, suffix: u8) {
+        if self.offset == BLOCK_LEN {
+            self.compress();
+        }
+        self.block.xorin(&[suffix], self.offset, 1);
+        self.compress();
+        R::roll_c(&mut self.mask);
+    }
+
+    /// Fills `output` with the output of the deck function over all ended strings, starting
+    /// `offset` bytes into the output stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last string has input that has not been ended.
+    pub fn expand(&self, offset: usize, output: &mut [u8]) {
+        for byte in output.iter_mut() {
+            *byte = 0;
+        }
+        self.expand_xor(offset, output);
+    }
+
+    pub(crate) fn expand_xor(&self, offset: usize, data: &mut [u8]) {
+        assert!(
+            self.offset == 0,
+            "the last string must be ended before expanding"
+        );
+        let mut state = self.accumulator;
+        P::execute(&mut state);
+        for _ in 0..offset / BLOCK_LEN {
+            R::roll_e(&mut state);
+        }
+
+        let mut skip = offset % BLOCK_LEN;
+        let mut data = data;
+        while !data.is_empty() {
+            let mut block = Buffer(state);
+            P::execute(block.words());
+            for (word, mask) in block.words().iter_mut().zip(self.mask.iter()) {
+                *word ^= *mask;
+            }
+
+            let len = core::cmp::min(BLOCK_LEN - skip, data.len());
+            let (head, tail) = data.split_at_mut(len);
+            block.execute(skip, len, |buffer| {
+                for (d, b) in head.iter_mut().zip(buffer.iter()) {
+                    *d ^= *b;
+                }
+            });
+            data = tail;
+            skip = 0;
+            R::roll_e(&mut state);
+        }
+    }
+
+    fn compress(&mut self) {
+        for (word, mask) in self.block.words().iter_mut().zip(self.mask.iter()) {
+            *word ^= *mask;
+        }
+        P::execute(self.block.words());
+        for (accumulator, word) in self.accumulator.iter_mut().zip(self.block.words().iter()) {
+            *accumulator ^= *word;
+        }
+        self.block = Buffer::default();
+        self.offset = 0;
+        R::roll_c(&mut self.mask);
+    }
+}
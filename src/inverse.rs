@@ -0,0 +1,129 @@
+//! The inverse of `keccak-p[1600, n_r]`, for state recovery and cryptanalysis.
+//!
+//! Every round is undone step by step: `iota`, `chi`, `pi` with `rho`, and `theta`, in reverse
+//! order. Unlike the other steps, the inverse of `theta` is dense, so it is much slower than
+//! `theta` itself.
+
+use crate::{MAX_ROUNDS, PI, RC, RHO, WORDS};
+
+/// Multiplies two elements of `GF(2)[x, z] / (x^5 + 1, z^64 + 1)`.
+///
+/// Every element is stored as 5 lanes, lane `i` holding the coefficients of `x^i z^0` to
+/// `x^i z^63`, which is how `theta` sees the parities of the 5 columns of a slice.
+const fn multiply(a: [u64; 5], b: [u64; 5]) -> [u64; 5] {
+    let mut c = [0u64; 5];
+    let mut i = 0;
+    while i < 5 {
+        let mut j = 0;
+        while j < 5 {
+            let mut t = 0;
+            while t < 64 {
+                if (a[i] >> t) & 1 == 1 {
+                    c[(i + j) % 5] ^= b[j].rotate_left(t);
+                }
+                t += 1;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    c
+}
+
+/// `theta` multiplies the column parities by `1 + x + x^4 z`. The group of units of the ring has
+/// exponent 960, so its inverse is its 959th power.
+const fn theta_inverse() -> [u64; 5] {
+    let mut base = [1, 1, 0, 0, 2];
+    let mut result = [1, 0, 0, 0, 0];
+    let mut exponent = 959;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base);
+        }
+        base = multiply(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+const THETA_INVERSE: [u64; 5] = theta_inverse();
+
+/// Undoes one round with the round constant `rc`.
+#[inline]
+fn round_inverse(a: &mut [u64; WORDS], rc: u64) {
+    // Iota
+    a[0] ^= rc;
+
+    // Chi
+    for y in 0..5 {
+        let mut row = [0u64; 5];
+        row.copy_from_slice(&a[5 * y..][..5]);
+        for x in 0..5 {
+            a[5 * y + x] = row[x]
+                ^ (!row[(x + 1) % 5] & (row[(x + 2) % 5] ^ (!row[(x + 3) % 5] & row[(x + 4) % 5])));
+        }
+    }
+
+    // Pi and rho
+    let b = *a;
+    let mut last = 1;
+    for (pi, rho) in PI.iter().zip(RHO.iter()) {
+        a[last] = b[*pi].rotate_right(*rho);
+        last = *pi;
+    }
+
+    // Theta
+    let mut parity = [0u64; 5];
+    for (x, column) in parity.iter_mut().enumerate() {
+        *column = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+    }
+    // the column parities before theta
+    let mut array = [0u64; 5];
+    for (i, inverse) in THETA_INVERSE.iter().enumerate() {
+        for t in 0..64 {
+            if (inverse >> t) & 1 == 1 {
+                for (x, column) in array.iter_mut().enumerate() {
+                    *column ^= parity[(x + 5 - i) % 5].rotate_left(t);
+                }
+            }
+        }
+    }
+    for x in 0..5 {
+        let d = array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+        for y in 0..5 {
+            a[5 * y + x] ^= d;
+        }
+    }
+}
+
+/// The inverse of [`keccak_p`], `keccak-p[1600, n_r]` with an arbitrary number of rounds `n_r`.
+///
+/// `keccak_p_inverse(state, rounds)` undoes `keccak_p(state, rounds)`.
+///
+/// # Panics
+///
+/// Panics if `rounds` is not in range `1..=24`.
+///
+/// [`keccak_p`]: fn.keccak_p.html
+pub fn keccak_p_inverse(a: &mut [u64; WORDS], rounds: usize) {
+    assert!(
+        (1..=MAX_ROUNDS).contains(&rounds),
+        "number of rounds must be in range 1..=24"
+    );
+    for rc in RC[MAX_ROUNDS - rounds..].iter().rev() {
+        round_inverse(a, *rc);
+    }
+}
+
+/// The inverse of [`keccak_p_rounds`], with the number of rounds known at compile time.
+///
+/// See [`keccak_p_inverse`] for details. Like for [`keccak_p_rounds`], a `ROUNDS` outside of
+/// range `1..=24` fails to compile.
+///
+/// [`keccak_p_rounds`]: fn.keccak_p_rounds.html
+/// [`keccak_p_inverse`]: fn.keccak_p_inverse.html
+#[inline]
+pub fn keccak_p_rounds_inverse<const ROUNDS: usize>(a: &mut [u64; WORDS]) {
+    let () = crate::CheckRounds::<ROUNDS>::OK;
+    keccak_p_inverse(a, ROUNDS);
+}
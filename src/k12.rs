@@ -2,7 +2,14 @@
 //!
 //! [`here`]: https://eprint.iacr.org/2016/770.pdf
 
-use crate::{bits_to_rate, keccakp::KeccakP, EncodedLen, Hasher, IntoXof, KeccakState, Xof};
+#[cfg(feature = "parallel")]
+use crate::times::sponge_batch_parallel;
+use crate::{
+    bits_to_rate,
+    keccakp::KeccakP,
+    times::{sponge_batch, PermutationTimes},
+    EncodedLen, Hasher, IntoXof, KeccakState, Permutation, Xof,
+};
 
 fn encode_len(len: usize) -> EncodedLen {
     let len_view = (len as u64).to_be_bytes();
@@ -14,45 +21,53 @@ fn encode_len(len: usize) -> EncodedLen {
     EncodedLen { offset, buffer }
 }
 
-/// The `KangarooTwelve` hash function defined [`here`].
-///
-/// # Usage
-///
-/// ```toml
-/// [dependencies]
-/// tiny-keccak = { version = "2.0.0", features = ["k12"] }
-/// ```
-///
-/// [`here`]: https://eprint.iacr.org/2016/770.pdf
-#[derive(Clone)]
-pub struct KangarooTwelve<T> {
-    state: KeccakState<KeccakP>,
-    current_chunk: KeccakState<KeccakP>,
+pub(crate) struct Tree<P: Permutation, T> {
+    state: KeccakState<P>,
+    current_chunk: KeccakState<P>,
     custom_string: Option<T>,
     written: usize,
     chunks: usize,
+    chaining_value_len: usize,
 }
 
-impl<T> KangarooTwelve<T> {
+impl<P: Permutation, T: Clone> Clone for Tree<P, T> {
+    fn clone(&self) -> Self {
+        Tree {
+            state: self.state.clone(),
+            current_chunk: self.current_chunk.clone(),
+            custom_string: self.custom_string.clone(),
+            written: self.written,
+            chunks: self.chunks,
+            chaining_value_len: self.chaining_value_len,
+        }
+    }
+}
+
+impl<P: Permutation, T> Tree<P, T> {
     const MAX_CHUNK_SIZE: usize = 8192;
 
-    /// Creates  new [`KangarooTwelve`] hasher with a security level of 128 bits.
-    ///
-    /// [`KangarooTwelve`]: struct.KangarooTwelve.html
-    pub fn new(custom_string: T) -> Self {
-        let rate = bits_to_rate(128);
-        KangarooTwelve {
+    /// The number of chunks hashed in parallel before their chaining values are absorbed,
+    /// which bounds the memory used for them.
+    #[cfg(feature = "parallel")]
+    const PARALLEL_CHUNKS: usize = 8192;
+
+    pub(crate) fn new(custom_string: T, bits: usize) -> Self {
+        let rate = bits_to_rate(bits);
+        Tree {
             state: KeccakState::new(rate, 0),
             current_chunk: KeccakState::new(rate, 0x0b),
             custom_string: Some(custom_string),
             written: 0,
             chunks: 0,
+            // 128 => 32, 256 => 64
+            chaining_value_len: bits / 4,
         }
     }
 }
 
-impl<T: AsRef<[u8]>> Hasher for KangarooTwelve<T> {
-    fn update(&mut self, input: &[u8]) {
+impl<P: PermutationTimes, T: AsRef<[u8]>> Tree<P, T> {
+    #[allow(clippy::len_zero)]
+    pub(crate) fn update(&mut self, input: &[u8]) {
         let mut to_absorb = input;
         if self.chunks == 0 {
             let todo = core::cmp::min(Self::MAX_CHUNK_SIZE - self.written, to_absorb.len());
@@ -61,21 +76,37 @@ impl<T: AsRef<[u8]>> Hasher for KangarooTwelve<T> {
             to_absorb = &to_absorb[todo..];
 
             if to_absorb.len() > 0 && self.written == Self::MAX_CHUNK_SIZE {
-                self.state.update(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
-                self.written = 0;
-                self.chunks += 1;
+                self.end_chunk();
             }
         }
 
         while to_absorb.len() > 0 {
             if self.written == Self::MAX_CHUNK_SIZE {
-                let mut chunk_hash = [0u8; 32];
-                let current_chunk = self.current_chunk.clone();
-                self.current_chunk.reset();
-                current_chunk.finalize(&mut chunk_hash);
-                self.state.update(&chunk_hash);
-                self.written = 0;
-                self.chunks += 1;
+                self.end_chunk();
+            }
+
+            // hash up to 8 whole chunks at once, leaving at least one byte for the current
+            // chunk, since the last chunk is hashed only when the input ends
+            let chunks = core::cmp::min(8, (to_absorb.len() - 1) / Self::MAX_CHUNK_SIZE);
+            if self.written == 0 && chunks >= 2 {
+                let (input, rest) = to_absorb.split_at(chunks * Self::MAX_CHUNK_SIZE);
+                let mut inputs: [&[u8]; 8] = Default::default();
+                for (dst, chunk) in inputs.iter_mut().zip(input.chunks(Self::MAX_CHUNK_SIZE)) {
+                    *dst = chunk;
+                }
+                let mut chunk_hashes = [[0u8; 64]; 8];
+                sponge_batch::<P, _, _>(
+                    self.current_chunk.rate,
+                    self.current_chunk.delim,
+                    &inputs[..chunks],
+                    &mut chunk_hashes[..chunks],
+                );
+                for chunk_hash in &chunk_hashes[..chunks] {
+                    self.state.update(&chunk_hash[..self.chaining_value_len]);
+                }
+                self.chunks += chunks;
+                to_absorb = rest;
+                continue;
             }
 
             let todo = core::cmp::min(Self::MAX_CHUNK_SIZE - self.written, to_absorb.len());
@@ -85,6 +116,138 @@ impl<T: AsRef<[u8]>> Hasher for KangarooTwelve<T> {
         }
     }
 
+    /// Absorbs `input` like [`update`], hashing its whole chunks on the `rayon` thread pool.
+    ///
+    /// [`update`]: #method.update
+    #[cfg(feature = "parallel")]
+    pub(crate) fn update_parallel(&mut self, input: &[u8]) {
+        // fill the current chunk first, so the remaining input starts at a chunk boundary
+        let todo = core::cmp::min(Self::MAX_CHUNK_SIZE - self.written, input.len());
+        self.update(&input[..todo]);
+        let input = &input[todo..];
+
+        // as in `update`, the last chunk is left to the sequential path
+        let chunks = input.len().saturating_sub(1) / Self::MAX_CHUNK_SIZE;
+        if chunks == 0 {
+            self.update(input);
+            return;
+        }
+
+        self.end_chunk();
+        let (input, rest) = input.split_at(chunks * Self::MAX_CHUNK_SIZE);
+        let window = core::cmp::min(chunks, Self::PARALLEL_CHUNKS);
+        let mut chunk_hashes = std::vec![[0u8; 64]; window];
+        for input in input.chunks(window * Self::MAX_CHUNK_SIZE) {
+            let chunk_hashes = &mut chunk_hashes[..input.len() / Self::MAX_CHUNK_SIZE];
+            sponge_batch_parallel::<P>(
+                self.current_chunk.rate,
+                self.current_chunk.delim,
+                input,
+                Self::MAX_CHUNK_SIZE,
+                chunk_hashes,
+            );
+            for chunk_hash in chunk_hashes.iter() {
+                self.state.update(&chunk_hash[..self.chaining_value_len]);
+            }
+            self.chunks += chunk_hashes.len();
+        }
+        self.update(rest);
+    }
+
+    /// Absorbs the chaining value of the full current chunk, or the padding of the first chunk,
+    /// into the final node and starts a new chunk.
+    fn end_chunk(&mut self) {
+        if self.chunks == 0 {
+            self.state.update(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+        } else {
+            let mut chunk_hash = [0u8; 64];
+            self.current_chunk
+                .squeeze(&mut chunk_hash[..self.chaining_value_len]);
+            self.current_chunk.reset();
+            self.state.update(&chunk_hash[..self.chaining_value_len]);
+        }
+        self.written = 0;
+        self.chunks += 1;
+    }
+
+    pub(crate) fn into_state(mut self) -> KeccakState<P> {
+        let custom_string = self
+            .custom_string
+            .take()
+            .expect("KangarooTwelve cannot be initialized without custom_string; qed");
+        let encoded_len = encode_len(custom_string.as_ref().len());
+        self.update(custom_string.as_ref());
+        self.update(encoded_len.value());
+
+        if self.chunks == 0 {
+            self.state.delim = 0x07;
+        } else {
+            let encoded_chunks = encode_len(self.chunks);
+            let mut tmp_chunk = [0u8; 64];
+            self.current_chunk
+                .finalize(&mut tmp_chunk[..self.chaining_value_len]);
+            self.state.update(&tmp_chunk[..self.chaining_value_len]);
+            self.state.update(encoded_chunks.value());
+            self.state.update(&[0xff, 0xff]);
+            self.state.delim = 0x06;
+        }
+
+        self.state
+    }
+}
+
+/// The `KangarooTwelve` hash function defined [`here`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["k12"] }
+/// ```
+///
+/// [`here`]: https://eprint.iacr.org/2016/770.pdf
+#[derive(Clone)]
+pub struct KangarooTwelve<T> {
+    tree: Tree<KeccakP, T>,
+}
+
+impl<T> KangarooTwelve<T> {
+    /// Creates  new [`KangarooTwelve`] hasher with a security level of 128 bits.
+    ///
+    /// [`KangarooTwelve`]: struct.KangarooTwelve.html
+    pub fn new(custom_string: T) -> Self {
+        KangarooTwelve {
+            tree: Tree::new(custom_string, 128),
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: AsRef<[u8]>> KangarooTwelve<T> {
+    /// Hashes `input` with the customization string `custom_string` into `output`, spreading the
+    /// chunks of long inputs across the `rayon` thread pool. The output is identical to the one
+    /// of the sequential [`Hasher`] interface.
+    ///
+    /// # Usage
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// tiny-keccak = { version = "2.0.0", features = ["k12", "parallel"] }
+    /// ```
+    ///
+    /// [`Hasher`]: trait.Hasher.html
+    pub fn hash_parallel(input: &[u8], custom_string: T, output: &mut [u8]) {
+        let mut tree = Tree::<KeccakP, T>::new(custom_string, 128);
+        tree.update_parallel(input);
+        tree.into_state().squeeze(output);
+    }
+}
+
+impl<T: AsRef<[u8]>> Hasher for KangarooTwelve<T> {
+    fn update(&mut self, input: &[u8]) {
+        self.tree.update(input);
+    }
+
     fn finalize(self, output: &mut [u8]) {
         let mut xof = self.into_xof();
         xof.squeeze(output);
@@ -128,32 +291,126 @@ pub struct KangarooTwelveXof {
 impl<T: AsRef<[u8]>> IntoXof for KangarooTwelve<T> {
     type Xof = KangarooTwelveXof;
 
-    fn into_xof(mut self) -> KangarooTwelveXof {
-        let custom_string = self
-            .custom_string
-            .take()
-            .expect("KangarooTwelve cannot be initialized without custom_string; qed");
-        let encoded_len = encode_len(custom_string.as_ref().len());
-        self.update(custom_string.as_ref());
-        self.update(encoded_len.value());
+    fn into_xof(self) -> KangarooTwelveXof {
+        KangarooTwelveXof {
+            state: self.tree.into_state(),
+        }
+    }
+}
 
-        if self.chunks == 0 {
-            self.state.delim = 0x07;
-        } else {
-            let encoded_chunks = encode_len(self.chunks);
-            let mut tmp_chunk = [0u8; 32];
-            self.current_chunk.finalize(&mut tmp_chunk);
-            self.state.update(&tmp_chunk);
-            self.state.update(encoded_chunks.value());
-            self.state.update(&[0xff, 0xff]);
-            self.state.delim = 0x06;
+impl Xof for KangarooTwelveXof {
+    fn squeeze(&mut self, output: &mut [u8]) {
+        self.state.squeeze(output);
+    }
+}
+
+/// The `KT256` hash function defined in [`RFC 9861`].
+///
+/// `KT256` is the 256-bit security variant of [`KangarooTwelve`]. It uses the same tree
+/// hashing mode, but with a capacity of 512 bits and 64-byte chaining values.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["k12"] }
+/// ```
+///
+/// [`RFC 9861`]: https://www.rfc-editor.org/rfc/rfc9861
+/// [`KangarooTwelve`]: struct.KangarooTwelve.html
+#[derive(Clone)]
+pub struct KangarooTwelve256<T> {
+    tree: Tree<KeccakP, T>,
+}
+
+impl<T> KangarooTwelve256<T> {
+    /// Creates  new [`KangarooTwelve256`] hasher with a security level of 256 bits.
+    ///
+    /// [`KangarooTwelve256`]: struct.KangarooTwelve256.html
+    pub fn new(custom_string: T) -> Self {
+        KangarooTwelve256 {
+            tree: Tree::new(custom_string, 256),
         }
+    }
+}
 
-        KangarooTwelveXof { state: self.state }
+#[cfg(feature = "parallel")]
+impl<T: AsRef<[u8]>> KangarooTwelve256<T> {
+    /// Hashes `input` with the customization string `custom_string` into `output`, spreading the
+    /// chunks of long inputs across the `rayon` thread pool. The output is identical to the one
+    /// of the sequential [`Hasher`] interface.
+    ///
+    /// # Usage
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// tiny-keccak = { version = "2.0.0", features = ["k12", "parallel"] }
+    /// ```
+    ///
+    /// [`Hasher`]: trait.Hasher.html
+    pub fn hash_parallel(input: &[u8], custom_string: T, output: &mut [u8]) {
+        let mut tree = Tree::<KeccakP, T>::new(custom_string, 256);
+        tree.update_parallel(input);
+        tree.into_state().squeeze(output);
     }
 }
 
-impl Xof for KangarooTwelveXof {
+impl<T: AsRef<[u8]>> Hasher for KangarooTwelve256<T> {
+    fn update(&mut self, input: &[u8]) {
+        self.tree.update(input);
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        let mut xof = self.into_xof();
+        xof.squeeze(output);
+    }
+}
+
+/// The `KT256` extendable-output function defined in [`RFC 9861`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["k12"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{KangarooTwelve256, Xof, IntoXof, Hasher};
+/// let input = b"hello world";
+/// let mut output = [0u8; 128];
+/// let mut hasher = KangarooTwelve256::new(b"");
+/// hasher.update(input);
+/// let mut xof = hasher.into_xof();
+/// xof.squeeze(&mut output[..64]);
+/// xof.squeeze(&mut output[64..]);
+/// ```
+///
+/// ---
+///
+/// [`KangarooTwelve256Xof`] can be created only by using [`KangarooTwelve256::IntoXof`] interface.
+///
+/// [`RFC 9861`]: https://www.rfc-editor.org/rfc/rfc9861
+/// [`KangarooTwelve256Xof`]: struct.KangarooTwelve256Xof.html
+/// [`KangarooTwelve256::IntoXof`]: struct.KangarooTwelve256.html#impl-IntoXof
+#[derive(Clone)]
+pub struct KangarooTwelve256Xof {
+    state: KeccakState<KeccakP>,
+}
+
+impl<T: AsRef<[u8]>> IntoXof for KangarooTwelve256<T> {
+    type Xof = KangarooTwelve256Xof;
+
+    fn into_xof(self) -> KangarooTwelve256Xof {
+        KangarooTwelve256Xof {
+            state: self.tree.into_state(),
+        }
+    }
+}
+
+impl Xof for KangarooTwelve256Xof {
     fn squeeze(&mut self, output: &mut [u8]) {
         self.state.squeeze(output);
     }
@@ -1,6 +1,9 @@
 //! The `Keccak` hash functions.
 
-use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
+use super::{
+    bits_to_rate, keccakf::KeccakF, times::sponge_batch, Hasher, KeccakFNative, KeccakState,
+};
+use core::task::Poll;
 
 /// The `Keccak` hash functions defined in [`Keccak SHA3 submission`].
 ///
@@ -14,7 +17,7 @@ use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
 /// [`Keccak SHA3 submission`]: https://keccak.team/files/Keccak-submission-3.pdf
 #[derive(Clone)]
 pub struct Keccak {
-    state: KeccakState<KeccakF>,
+    state: KeccakState<KeccakFNative>,
 }
 
 impl Keccak {
@@ -48,11 +51,118 @@ impl Keccak {
         Keccak::new(512)
     }
 
+    /// Hashes every input with [`Keccak`]-224 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v224_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 28]]) {
+        sponge_batch::<KeccakF, _, _>(bits_to_rate(224), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Keccak`]-256 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v256_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 32]]) {
+        sponge_batch::<KeccakF, _, _>(bits_to_rate(256), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Keccak`]-384 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v384_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 48]]) {
+        sponge_batch::<KeccakF, _, _>(bits_to_rate(384), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Keccak`]-512 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn v512_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 64]]) {
+        sponge_batch::<KeccakF, _, _>(bits_to_rate(512), Self::DELIM, inputs, outputs);
+    }
+
     fn new(bits: usize) -> Keccak {
         Keccak {
             state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
         }
     }
+
+    /// Absorbs `input` like [`update`], but applies at most `rounds` rounds of the permutation
+    /// per call, so that hashing long inputs can be interleaved with other work.
+    ///
+    /// Returns [`Poll::Pending`] until all of `input` is absorbed. Until then, it must be called
+    /// again with the same `input`, and no other method of the hasher may be called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Keccak};
+    /// # use core::task::Poll;
+    /// let input = [0x42u8; 1000];
+    /// let mut output = [0u8; 32];
+    /// let mut keccak = Keccak::v256();
+    /// while keccak.update_step(&input, 4) == Poll::Pending {
+    ///     // other work
+    /// }
+    /// let mut pending = keccak.finalize_step(&mut output, 4);
+    /// while let Some(keccak) = pending {
+    ///     // other work
+    ///     pending = keccak.finalize_step(&mut output, 4);
+    /// }
+    ///
+    /// let mut expected = [0u8; 32];
+    /// let mut keccak = Keccak::v256();
+    /// keccak.update(&input);
+    /// keccak.finalize(&mut expected);
+    /// assert_eq!(expected, output);
+    /// ```
+    ///
+    /// [`update`]: trait.Hasher.html#tymethod.update
+    /// [`Poll::Pending`]: https://doc.rust-lang.org/core/task/enum.Poll.html
+    pub fn update_step(&mut self, input: &[u8], rounds: usize) -> Poll<()> {
+        self.state.update_step(input, rounds)
+    }
+
+    /// Pads and squeezes the digest like [`finalize`], but applies at most `rounds` rounds of
+    /// the permutation per call.
+    ///
+    /// Hands the hasher back until the whole digest is written. Until then, it must be called
+    /// again on the returned hasher with the same `output`. Once it returns `None`, the hasher
+    /// is consumed, like by [`finalize`].
+    ///
+    /// [`finalize`]: trait.Hasher.html#tymethod.finalize
+    pub fn finalize_step(mut self, output: &mut [u8], rounds: usize) -> Option<Keccak> {
+        match self.state.squeeze_step(output, rounds) {
+            Poll::Pending => Some(self),
+            Poll::Ready(()) => None,
+        }
+    }
 }
 
 impl Hasher for Keccak {
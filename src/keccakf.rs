@@ -1,40 +1,45 @@
-use crate::{Buffer, Permutation};
+use crate::{resumable::ResumablePermutation, round_constants, Permutation, WORDS};
+use core::ops::Range;
 
 const ROUNDS: usize = 24;
 
-const RC: [u64; ROUNDS] = [
-    1u64,
-    0x8082u64,
-    0x800000000000808au64,
-    0x8000000080008000u64,
-    0x808bu64,
-    0x80000001u64,
-    0x8000000080008081u64,
-    0x8000000000008009u64,
-    0x8au64,
-    0x88u64,
-    0x80008009u64,
-    0x8000000au64,
-    0x8000808bu64,
-    0x800000000000008bu64,
-    0x8000000000008089u64,
-    0x8000000000008003u64,
-    0x8000000000008002u64,
-    0x8000000000000080u64,
-    0x800au64,
-    0x800000008000000au64,
-    0x8000000080008081u64,
-    0x8000000000008080u64,
-    0x80000001u64,
-    0x8000000080008008u64,
-];
+const RC: [u64; ROUNDS] = round_constants();
 
 keccak_function!("`keccak-f[1600, 24]`", keccakf, ROUNDS, RC);
 
+/// The inverse of [`keccakf`], `keccak-f[1600, 24]`.
+///
+/// [`keccakf`]: fn.keccakf.html
+pub fn keccakf_inverse(a: &mut [u64; WORDS]) {
+    crate::keccak_p_inverse(a, ROUNDS);
+}
+
+/// The `keccak-f[1600, 24]` permutation.
+#[derive(Clone, Copy, Debug)]
 pub struct KeccakF;
 
 impl Permutation for KeccakF {
-    fn execute(buffer: &mut Buffer) {
-        keccakf(buffer.words());
+    type Lane = u64;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakf(state);
     }
 }
+
+impl ResumablePermutation for KeccakF {
+    const ROUNDS: usize = ROUNDS;
+
+    fn execute_rounds(state: &mut [Self::Lane; WORDS], rounds: Range<usize>) {
+        crate::keccak_rounds(state, &RC[rounds]);
+    }
+}
+
+#[cfg(any(
+    feature = "keccak",
+    feature = "sha3",
+    feature = "shake",
+    feature = "parallel_hash"
+))]
+impl crate::times::PermutationTimes for KeccakF {
+    const ROUNDS: usize = ROUNDS;
+}
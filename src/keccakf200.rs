@@ -0,0 +1,19 @@
+use crate::{Permutation, WORDS};
+
+const ROUNDS: usize = 18;
+
+const RC: [u8; ROUNDS] = truncated_round_constants!(u8, ROUNDS);
+
+keccak_function!("`keccak-f[200, 18]`", keccakf200, u8, ROUNDS, RC);
+
+/// The `keccak-f[200, 18]` permutation.
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakF200;
+
+impl Permutation for KeccakF200 {
+    type Lane = u8;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakf200(state);
+    }
+}
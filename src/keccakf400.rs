@@ -0,0 +1,19 @@
+use crate::{Permutation, WORDS};
+
+const ROUNDS: usize = 20;
+
+const RC: [u16; ROUNDS] = truncated_round_constants!(u16, ROUNDS);
+
+keccak_function!("`keccak-f[400, 20]`", keccakf400, u16, ROUNDS, RC);
+
+/// The `keccak-f[400, 20]` permutation.
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakF400;
+
+impl Permutation for KeccakF400 {
+    type Lane = u16;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakf400(state);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{Permutation, WORDS};
+
+const ROUNDS: usize = 22;
+
+const RC: [u32; ROUNDS] = truncated_round_constants!(u32, ROUNDS);
+
+keccak_function!("`keccak-f[800, 22]`", keccakf800, u32, ROUNDS, RC);
+
+/// The `keccak-f[800, 22]` permutation.
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakF800;
+
+impl Permutation for KeccakF800 {
+    type Lane = u32;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakf800(state);
+    }
+}
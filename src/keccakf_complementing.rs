@@ -0,0 +1,295 @@
+//! `keccak-f[1600, 24]` with lane complementing.
+//!
+//! The lanes `1`, `2`, `8`, `12`, `17` and `20` of the state are kept complemented, which lets
+//! `Chi` complement a single lane of every row instead of all five. In rows `2`, `3` and `4` that
+//! lane is an input of two outputs, so these rows use its complement twice. The state stays in
+//! this representation between permutations; [`Buffer`] converts only the lanes it reads and
+//! writes, and starts from the complemented zero state.
+//!
+//! [`Buffer`]: ../struct.Buffer.html
+
+use crate::{
+    resumable::ResumablePermutation, round_constants, sealed, Lane, Permutation, PI, RHO, WORDS,
+};
+use core::ops::Range;
+
+const ROUNDS: usize = 24;
+
+const RC: [u64; ROUNDS] = round_constants();
+
+/// The lanes kept complemented, one bit per lane.
+const COMPLEMENTED: u32 = 1 << 1 | 1 << 2 | 1 << 8 | 1 << 12 | 1 << 17 | 1 << 20;
+
+/// Returns the mask complementing the lane `index` in and out of the complemented
+/// representation.
+#[inline(always)]
+const fn mask(index: usize) -> u64 {
+    if COMPLEMENTED >> index & 1 == 1 {
+        !0
+    } else {
+        0
+    }
+}
+
+/// How `Chi` computes an output lane from the lanes `b[x]`, `b[x + 1]` and `b[x + 2]` of its
+/// row, as stored after `Theta`, `Rho` and `Pi`: the output is `b[x] ^ (b[x + 1] & b[x + 2])`, or
+/// `b[x] ^ (b[x + 1] | b[x + 2])`, after complementing the selected inputs.
+#[derive(Clone, Copy)]
+struct Chi {
+    not: [bool; 3],
+    or: bool,
+}
+
+/// Derived for the complemented lanes above, so that each row complements a single lane and the
+/// output is again in the complemented representation. The complemented lane is `b[2]` in row
+/// `0`, `b[4]` in row `1`, `b[3]` in rows `2` and `3`, and `b[1]` in row `4`; in the last three
+/// rows it is an input of two outputs.
+const CHI: [Chi; WORDS] = [
+    // y = 0
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, true, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    // y = 1
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    Chi {
+        not: [false, false, true],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    // y = 2
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    Chi {
+        not: [false, true, false],
+        or: false,
+    },
+    Chi {
+        not: [true, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    // y = 3
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, true, false],
+        or: true,
+    },
+    Chi {
+        not: [true, false, false],
+        or: false,
+    },
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    // y = 4
+    Chi {
+        not: [false, true, false],
+        or: false,
+    },
+    Chi {
+        not: [true, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+    Chi {
+        not: [false, false, false],
+        or: true,
+    },
+    Chi {
+        not: [false, false, false],
+        or: false,
+    },
+];
+
+/// A lane of a state whose [`COMPLEMENTED`] lanes are stored complemented.
+///
+/// [`COMPLEMENTED`]: constant.COMPLEMENTED.html
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(transparent)]
+pub(crate) struct Complemented(pub(crate) u64);
+
+impl sealed::Sealed for Complemented {
+    #[inline]
+    fn to_le_lane(self, index: usize) -> Self {
+        Complemented((self.0 ^ mask(index)).to_le())
+    }
+
+    #[inline]
+    fn to_native_lane(self, index: usize) -> Self {
+        Complemented(u64::from_le(self.0) ^ mask(index))
+    }
+
+    #[inline]
+    fn xor_le_bytes(self, bytes: &[u8], _index: usize) -> Self {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(bytes);
+        // complementing is a xor too, so the input can be added to the complemented lane
+        Complemented(self.0 ^ u64::from_le_bytes(le))
+    }
+
+    #[inline]
+    fn write_le_bytes(self, bytes: &mut [u8], index: usize) {
+        bytes.copy_from_slice(&(self.0 ^ mask(index)).to_le_bytes());
+    }
+}
+
+impl Lane for Complemented {}
+
+/// Applies one round on a state with complemented lanes for every round constant in `rc`.
+#[allow(unused_assignments)]
+fn keccakf_complemented(a: &mut [u64; WORDS], rc: &[u64]) {
+    use crunchy::unroll;
+
+    for rc in rc {
+        let mut array = [0u64; 5];
+
+        // Theta
+        unroll! {
+            for x in 0..5 {
+                unroll! {
+                    for y_count in 0..5 {
+                        let y = y_count * 5;
+                        array[x] ^= a[x + y];
+                    }
+                }
+            }
+        }
+
+        unroll! {
+            for x in 0..5 {
+                unroll! {
+                    for y_count in 0..5 {
+                        let y = y_count * 5;
+                        a[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+                    }
+                }
+            }
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        unroll! {
+            for x in 0..24 {
+                array[0] = a[PI[x]];
+                a[PI[x]] = last.rotate_left(RHO[x]);
+                last = array[0];
+            }
+        }
+
+        // Chi
+        unroll! {
+            for y_step in 0..5 {
+                let y = y_step * 5;
+
+                unroll! {
+                    for x in 0..5 {
+                        array[x] = a[y + x];
+                    }
+                }
+
+                unroll! {
+                    for x in 0..5 {
+                        let chi = CHI[y + x];
+                        let not = |lane: u64, not: bool| if not { !lane } else { lane };
+                        let b0 = not(array[x], chi.not[0]);
+                        let b1 = not(array[(x + 1) % 5], chi.not[1]);
+                        let b2 = not(array[(x + 2) % 5], chi.not[2]);
+                        a[y + x] = b0 ^ if chi.or { b1 | b2 } else { b1 & b2 };
+                    }
+                }
+            }
+        }
+
+        // Iota
+        a[0] ^= rc;
+    }
+}
+
+/// `keccak-f[1600, 24]` computed with lane complementing.
+///
+/// The state is in the usual representation; the complemented lanes are converted on entry and
+/// exit. The sponges of this crate keep their state complemented between permutations instead.
+pub fn keccakf_complementing(a: &mut [u64; WORDS]) {
+    for (i, lane) in a.iter_mut().enumerate() {
+        *lane ^= mask(i);
+    }
+    keccakf_complemented(a, &RC);
+    for (i, lane) in a.iter_mut().enumerate() {
+        *lane ^= mask(i);
+    }
+}
+
+/// The `keccak-f[1600, 24]` permutation on a state with complemented lanes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct KeccakFComplementing;
+
+impl Permutation for KeccakFComplementing {
+    type Lane = Complemented;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        // `Complemented` is a transparent wrapper of `u64`
+        let state = unsafe { &mut *(state as *mut [Complemented; WORDS] as *mut [u64; WORDS]) };
+        keccakf_complemented(state, &RC);
+    }
+}
+
+impl ResumablePermutation for KeccakFComplementing {
+    const ROUNDS: usize = ROUNDS;
+
+    fn execute_rounds(state: &mut [Self::Lane; WORDS], rounds: Range<usize>) {
+        // `Complemented` is a transparent wrapper of `u64`
+        let state = unsafe { &mut *(state as *mut [Complemented; WORDS] as *mut [u64; WORDS]) };
+        keccakf_complemented(state, &RC[rounds]);
+    }
+}
@@ -0,0 +1,217 @@
+//! `keccak-f[1600, 24]` on bit-interleaved lanes, for targets without native 64-bit rotations.
+//!
+//! Every lane is split into a 32-bit word of its even bits and a 32-bit word of its odd bits, so
+//! a rotation of the 64-bit lane becomes a pair of 32-bit rotations. The state stays interleaved
+//! between permutations; [`Buffer`] converts only the lanes it reads and writes.
+//!
+//! [`Buffer`]: ../struct.Buffer.html
+
+use crate::{
+    resumable::ResumablePermutation, round_constants, sealed, Lane, Permutation, PI, RHO, WORDS,
+};
+use core::ops::Range;
+
+const ROUNDS: usize = 24;
+
+const RC: [u64; ROUNDS] = interleaved_round_constants();
+
+const fn interleaved_round_constants() -> [u64; ROUNDS] {
+    let mut rc = round_constants::<ROUNDS>();
+    let mut i = 0;
+    while i < ROUNDS {
+        rc[i] = interleave(rc[i]);
+        i += 1;
+    }
+    rc
+}
+
+/// Swaps the bits of `x` selected by `mask` with the bits `shift` positions higher.
+#[inline(always)]
+const fn delta_swap(x: u64, mask: u64, shift: u32) -> u64 {
+    let t = ((x >> shift) ^ x) & mask;
+    x ^ t ^ (t << shift)
+}
+
+/// Moves the even bits of `lane` into the low and the odd bits into the high 32 bits.
+#[inline]
+pub(crate) const fn interleave(lane: u64) -> u64 {
+    let lane = delta_swap(lane, 0x2222_2222_2222_2222, 1);
+    let lane = delta_swap(lane, 0x0c0c_0c0c_0c0c_0c0c, 2);
+    let lane = delta_swap(lane, 0x00f0_00f0_00f0_00f0, 4);
+    let lane = delta_swap(lane, 0x0000_ff00_0000_ff00, 8);
+    delta_swap(lane, 0x0000_0000_ffff_0000, 16)
+}
+
+/// The inverse of [`interleave`].
+///
+/// [`interleave`]: fn.interleave.html
+#[inline]
+pub(crate) const fn deinterleave(lane: u64) -> u64 {
+    let lane = delta_swap(lane, 0x0000_0000_ffff_0000, 16);
+    let lane = delta_swap(lane, 0x0000_ff00_0000_ff00, 8);
+    let lane = delta_swap(lane, 0x00f0_00f0_00f0_00f0, 4);
+    let lane = delta_swap(lane, 0x0c0c_0c0c_0c0c_0c0c, 2);
+    delta_swap(lane, 0x2222_2222_2222_2222, 1)
+}
+
+/// A 64-bit lane with its even bits in the low and its odd bits in the high 32 bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(transparent)]
+pub(crate) struct Interleaved(pub(crate) u64);
+
+impl sealed::Sealed for Interleaved {
+    #[inline]
+    fn to_le_lane(self, _index: usize) -> Self {
+        Interleaved(deinterleave(self.0).to_le())
+    }
+
+    #[inline]
+    fn to_native_lane(self, _index: usize) -> Self {
+        Interleaved(interleave(u64::from_le(self.0)))
+    }
+
+    #[inline]
+    fn xor_le_bytes(self, bytes: &[u8], _index: usize) -> Self {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(bytes);
+        // interleaving only moves bits, so it commutes with xor
+        Interleaved(self.0 ^ interleave(u64::from_le_bytes(le)))
+    }
+
+    #[inline]
+    fn write_le_bytes(self, bytes: &mut [u8], _index: usize) {
+        bytes.copy_from_slice(&deinterleave(self.0).to_le_bytes());
+    }
+}
+
+impl Lane for Interleaved {}
+
+/// Rotates the interleaved lane `(even, odd)` left by `n` bits.
+#[inline(always)]
+fn rotate_left(even: u32, odd: u32, n: u32) -> (u32, u32) {
+    if n & 1 == 0 {
+        (even.rotate_left(n / 2), odd.rotate_left(n / 2))
+    } else {
+        // the even bits become odd ones and vice versa
+        (odd.rotate_left(n / 2 + 1), even.rotate_left(n / 2))
+    }
+}
+
+/// `keccak-f[1600, 24]` on bit-interleaved lanes.
+pub(crate) fn keccakf_interleaved(a: &mut [Interleaved; WORDS]) {
+    keccakf_interleaved_rounds(a, &RC);
+}
+
+/// Applies one round on bit-interleaved lanes for every interleaved round constant in `rc`.
+#[allow(unused_assignments)]
+fn keccakf_interleaved_rounds(a: &mut [Interleaved; WORDS], rc: &[u64]) {
+    use crunchy::unroll;
+
+    let mut even = [0u32; WORDS];
+    let mut odd = [0u32; WORDS];
+    unroll! {
+        for i in 0..25 {
+            even[i] = a[i].0 as u32;
+            odd[i] = (a[i].0 >> 32) as u32;
+        }
+    }
+
+    for rc in rc {
+        let mut array_even = [0u32; 5];
+        let mut array_odd = [0u32; 5];
+
+        // Theta
+        unroll! {
+            for x in 0..5 {
+                unroll! {
+                    for y_count in 0..5 {
+                        let y = y_count * 5;
+                        array_even[x] ^= even[x + y];
+                        array_odd[x] ^= odd[x + y];
+                    }
+                }
+            }
+        }
+
+        unroll! {
+            for x in 0..5 {
+                let (d_even, d_odd) =
+                    rotate_left(array_even[(x + 1) % 5], array_odd[(x + 1) % 5], 1);
+                let d_even = d_even ^ array_even[(x + 4) % 5];
+                let d_odd = d_odd ^ array_odd[(x + 4) % 5];
+                unroll! {
+                    for y_count in 0..5 {
+                        let y = y_count * 5;
+                        even[y + x] ^= d_even;
+                        odd[y + x] ^= d_odd;
+                    }
+                }
+            }
+        }
+
+        // Rho and pi
+        let mut last = (even[1], odd[1]);
+        unroll! {
+            for x in 0..24 {
+                let current = (even[PI[x]], odd[PI[x]]);
+                let (e, o) = rotate_left(last.0, last.1, RHO[x]);
+                even[PI[x]] = e;
+                odd[PI[x]] = o;
+                last = current;
+            }
+        }
+
+        // Chi
+        unroll! {
+            for y_step in 0..5 {
+                let y = y_step * 5;
+
+                unroll! {
+                    for x in 0..5 {
+                        array_even[x] = even[y + x];
+                        array_odd[x] = odd[y + x];
+                    }
+                }
+
+                unroll! {
+                    for x in 0..5 {
+                        even[y + x] = array_even[x]
+                            ^ ((!array_even[(x + 1) % 5]) & (array_even[(x + 2) % 5]));
+                        odd[y + x] = array_odd[x]
+                            ^ ((!array_odd[(x + 1) % 5]) & (array_odd[(x + 2) % 5]));
+                    }
+                }
+            }
+        }
+
+        // Iota
+        even[0] ^= *rc as u32;
+        odd[0] ^= (*rc >> 32) as u32;
+    }
+
+    unroll! {
+        for i in 0..25 {
+            a[i] = Interleaved(u64::from(even[i]) | (u64::from(odd[i]) << 32));
+        }
+    }
+}
+
+/// The `keccak-f[1600, 24]` permutation on bit-interleaved lanes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct KeccakFInterleaved;
+
+impl Permutation for KeccakFInterleaved {
+    type Lane = Interleaved;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakf_interleaved(state);
+    }
+}
+
+impl ResumablePermutation for KeccakFInterleaved {
+    const ROUNDS: usize = ROUNDS;
+
+    fn execute_rounds(state: &mut [Self::Lane; WORDS], rounds: Range<usize>) {
+        keccakf_interleaved_rounds(state, &RC[rounds]);
+    }
+}
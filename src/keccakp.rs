@@ -1,28 +1,31 @@
-use crate::{Buffer, Permutation};
+use crate::{round_constants, Permutation, WORDS};
 
 const ROUNDS: usize = 12;
 
-const RC: [u64; ROUNDS] = [
-    0x000000008000808b,
-    0x800000000000008b,
-    0x8000000000008089,
-    0x8000000000008003,
-    0x8000000000008002,
-    0x8000000000000080,
-    0x000000000000800a,
-    0x800000008000000a,
-    0x8000000080008081,
-    0x8000000000008080,
-    0x0000000080000001,
-    0x8000000080008008,
-];
+const RC: [u64; ROUNDS] = round_constants();
 
 keccak_function!("`keccak-p[1600, 12]`", keccakp, ROUNDS, RC);
 
+/// The inverse of [`keccakp`], `keccak-p[1600, 12]`.
+///
+/// [`keccakp`]: fn.keccakp.html
+pub fn keccakp_inverse(a: &mut [u64; WORDS]) {
+    crate::keccak_p_inverse(a, ROUNDS);
+}
+
+/// The `keccak-p[1600, 12]` permutation.
+#[derive(Clone, Copy, Debug)]
 pub struct KeccakP;
 
 impl Permutation for KeccakP {
-    fn execute(buffer: &mut Buffer) {
-        keccakp(buffer.words());
+    type Lane = u64;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakp(state);
     }
 }
+
+#[cfg(feature = "k12")]
+impl crate::times::PermutationTimes for KeccakP {
+    const ROUNDS: usize = ROUNDS;
+}
@@ -0,0 +1,23 @@
+use crate::{round_constants, Permutation, WORDS};
+
+const ROUNDS: usize = 14;
+
+const RC: [u64; ROUNDS] = round_constants();
+
+keccak_function!("`keccak-p[1600, 14]`", keccakp14, ROUNDS, RC);
+
+/// The `keccak-p[1600, 14]` permutation.
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakP14;
+
+impl Permutation for KeccakP14 {
+    type Lane = u64;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakp14(state);
+    }
+}
+
+impl crate::times::PermutationTimes for KeccakP14 {
+    const ROUNDS: usize = ROUNDS;
+}
@@ -0,0 +1,19 @@
+use crate::{round_constants, Permutation, WORDS};
+
+const ROUNDS: usize = 6;
+
+const RC: [u64; ROUNDS] = round_constants();
+
+keccak_function!("`keccak-p[1600, 6]`", keccakp6, ROUNDS, RC);
+
+/// The `keccak-p[1600, 6]` permutation.
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakP6;
+
+impl Permutation for KeccakP6 {
+    type Lane = u64;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        keccakp6(state);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "fault_detection")]
+use crate::FaultDetected;
 use crate::{bits_to_rate, left_encode, right_encode, CShake, Hasher, IntoXof, Xof};
 
 /// The `KMAC` pseudo-random functions defined in [`SP800-185`].
@@ -51,6 +53,24 @@ impl Kmac {
         state.fill_block();
         Kmac { state }
     }
+
+    /// Pads and squeezes the output like [`finalize`], unless the redundant computation of any
+    /// permutation did not match. No byte of a faulty state is ever written to `output`; the
+    /// bytes squeezed before the fault was detected are zeroed.
+    ///
+    /// # Usage
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// tiny-keccak = { version = "2.0.0", features = ["kmac", "fault_detection"] }
+    /// ```
+    ///
+    /// [`finalize`]: trait.Hasher.html#tymethod.finalize
+    #[cfg(feature = "fault_detection")]
+    pub fn try_finalize(mut self, output: &mut [u8]) -> Result<(), FaultDetected> {
+        self.state.update(right_encode(output.len() * 8).value());
+        self.state.try_finalize(output)
+    }
 }
 
 impl Hasher for Kmac {
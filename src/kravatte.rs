@@ -0,0 +1,261 @@
+//! The `Kravatte` deck function and its authenticated encryption modes defined [`here`].
+//!
+//! [`here`]: https://keccak.team/files/Kravatte.pdf
+
+use crate::{farfalle::Farfalle, keccakp6::KeccakP6, InvalidTag, Rolling, WORDS};
+
+/// The rolling functions of `Kravatte Achouffe`.
+#[derive(Clone, Copy, Debug)]
+pub struct KravatteRolling;
+
+impl Rolling for KravatteRolling {
+    fn roll_c(state: &mut [u64; WORDS]) {
+        let x0 = state[20];
+        let x1 = state[21];
+        state[20..].rotate_left(1);
+        state[24] = x0.rotate_left(7) ^ x1 ^ (x1 >> 3);
+    }
+
+    fn roll_e(state: &mut [u64; WORDS]) {
+        let x0 = state[15];
+        let x1 = state[16];
+        let x2 = state[17];
+        state[15..].rotate_left(1);
+        state[24] = x0.rotate_left(7) ^ x1.rotate_left(18) ^ (x2 & (x1 >> 1));
+    }
+}
+
+/// The `Kravatte` deck function: [`Farfalle`] over [`keccakp6`] with the `Kravatte Achouffe`
+/// rolling functions.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["kravatte"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::Kravatte;
+/// let mut kravatte = Kravatte::new(b"secret key");
+/// let mut output = [0u8; 64];
+/// kravatte.update(b"hello world");
+/// kravatte.end_string();
+/// kravatte.expand(0, &mut output);
+/// ```
+///
+/// [`Farfalle`]: struct.Farfalle.html
+/// [`keccakp6`]: fn.keccakp6.html
+pub type Kravatte = Farfalle<KeccakP6, KravatteRolling>;
+
+fn verify(expected: &[u8], tag: &[u8], data: &mut [u8]) -> Result<(), InvalidTag> {
+    let diff = expected
+        .iter()
+        .zip(tag.iter())
+        .fold(0, |diff, (a, b)| diff | (a ^ b));
+    if diff != 0 {
+        for byte in data.iter_mut() {
+            *byte = 0;
+        }
+        return Err(InvalidTag);
+    }
+
+    Ok(())
+}
+
+/// The `Kravatte-SANE` session authenticated encryption scheme.
+///
+/// A session is started with a key and a nonce and can then wrap or unwrap a sequence of
+/// messages, each with its own associated data and tag. Every tag authenticates the nonce
+/// and all the messages of the session processed so far.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["kravatte"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::KravatteSane;
+/// let mut tag = [0u8; KravatteSane::TAG_LEN];
+/// let mut sender = KravatteSane::new(b"secret key", b"unique nonce", &mut tag);
+/// let mut receiver = KravatteSane::new(b"secret key", b"unique nonce", &mut tag);
+///
+/// let mut data = *b"hello world";
+/// sender.wrap(b"header", &mut data, &mut tag);
+/// receiver.unwrap(b"header", &mut data, &tag).unwrap();
+/// assert_eq!(b"hello world", &data);
+/// ```
+#[derive(Clone)]
+pub struct KravatteSane {
+    kravatte: Kravatte,
+    e: u8,
+}
+
+impl KravatteSane {
+    /// The length of the tag in bytes.
+    pub const TAG_LEN: usize = 16;
+
+    /// Starts new [`KravatteSane`] session and writes the tag of the nonce to `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not shorter than `200` bytes.
+    ///
+    /// [`KravatteSane`]: struct.KravatteSane.html
+    pub fn new(key: &[u8], nonce: &[u8], tag: &mut [u8; 16]) -> KravatteSane {
+        let mut kravatte = Kravatte::new(key);
+        kravatte.update(nonce);
+        kravatte.end_string();
+        kravatte.expand(0, tag);
+        KravatteSane { kravatte, e: 0 }
+    }
+
+    /// Encrypts `data` in place, authenticates it together with `ad` and writes the tag to
+    /// `tag`.
+    pub fn wrap(&mut self, ad: &[u8], data: &mut [u8], tag: &mut [u8; 16]) {
+        self.kravatte.expand_xor(Self::TAG_LEN, data);
+        self.absorb(ad, data);
+        self.kravatte.expand(0, tag);
+    }
+
+    /// Decrypts `data` in place and verifies it together with `ad` against `tag` in constant
+    /// time.
+    ///
+    /// If the tag does not match, `data` is zeroed and the session must not be used anymore.
+    pub fn unwrap(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8; 16]) -> Result<(), InvalidTag> {
+        let keystream = self.kravatte.clone();
+        self.absorb(ad, data);
+        keystream.expand_xor(Self::TAG_LEN, data);
+
+        let mut expected = [0u8; 16];
+        self.kravatte.expand(0, &mut expected);
+        verify(&expected, tag, data)
+    }
+
+    fn absorb(&mut self, ad: &[u8], ciphertext: &[u8]) {
+        if !ad.is_empty() || ciphertext.is_empty() {
+            self.kravatte.update(ad);
+            // the string is followed by the bits `0` and `e`
+            self.kravatte.end_string_with_suffix(0x04 | self.e << 1);
+        }
+        if !ciphertext.is_empty() {
+            self.kravatte.update(ciphertext);
+            // the string is followed by the bits `1` and `e`
+            self.kravatte.end_string_with_suffix(0x05 | self.e << 1);
+        }
+        self.e ^= 1;
+    }
+}
+
+/// The `Kravatte-SANSE` session authenticated encryption scheme.
+///
+/// Unlike [`KravatteSane`], `Kravatte-SANSE` does not take a nonce. It derives the keystream
+/// from the tag, which itself depends on the plaintext, so repeating a message only reveals
+/// that it was repeated.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["kravatte"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::KravatteSanse;
+/// let mut sender = KravatteSanse::new(b"secret key");
+/// let mut receiver = KravatteSanse::new(b"secret key");
+///
+/// let mut data = *b"hello world";
+/// let mut tag = [0u8; KravatteSanse::TAG_LEN];
+/// sender.wrap(b"header", &mut data, &mut tag);
+/// receiver.unwrap(b"header", &mut data, &tag).unwrap();
+/// assert_eq!(b"hello world", &data);
+/// ```
+///
+/// [`KravatteSane`]: struct.KravatteSane.html
+#[derive(Clone)]
+pub struct KravatteSanse {
+    kravatte: Kravatte,
+    e: u8,
+}
+
+impl KravatteSanse {
+    /// The length of the tag in bytes.
+    pub const TAG_LEN: usize = 32;
+
+    /// Starts new [`KravatteSanse`] session.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not shorter than `200` bytes.
+    ///
+    /// [`KravatteSanse`]: struct.KravatteSanse.html
+    pub fn new(key: &[u8]) -> KravatteSanse {
+        KravatteSanse {
+            kravatte: Kravatte::new(key),
+            e: 0,
+        }
+    }
+
+    /// Encrypts `data` in place, authenticates it together with `ad` and writes the tag to
+    /// `tag`.
+    pub fn wrap(&mut self, ad: &[u8], data: &mut [u8], tag: &mut [u8; 32]) {
+        self.absorb_ad(ad, data.is_empty());
+        if data.is_empty() {
+            self.kravatte.expand(0, tag);
+        } else {
+            let history = self.kravatte.clone();
+            self.absorb_plaintext(data);
+            self.kravatte.expand(0, tag);
+            self.keystream(history, tag).expand_xor(0, data);
+        }
+        self.e ^= 1;
+    }
+
+    /// Decrypts `data` in place and verifies it together with `ad` against `tag` in constant
+    /// time.
+    ///
+    /// If the tag does not match, `data` is zeroed and the session must not be used anymore.
+    pub fn unwrap(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8; 32]) -> Result<(), InvalidTag> {
+        self.absorb_ad(ad, data.is_empty());
+        if !data.is_empty() {
+            self.keystream(self.kravatte.clone(), tag)
+                .expand_xor(0, data);
+            self.absorb_plaintext(data);
+        }
+        self.e ^= 1;
+
+        let mut expected = [0u8; 32];
+        self.kravatte.expand(0, &mut expected);
+        verify(&expected, tag, data)
+    }
+
+    fn absorb_ad(&mut self, ad: &[u8], empty_plaintext: bool) {
+        if !ad.is_empty() || empty_plaintext {
+            self.kravatte.update(ad);
+            // the string is followed by the bits `0` and `e`
+            self.kravatte.end_string_with_suffix(0x04 | self.e << 1);
+        }
+    }
+
+    fn absorb_plaintext(&mut self, plaintext: &[u8]) {
+        self.kravatte.update(plaintext);
+        // the string is followed by the bits `0`, `1` and `e`
+        self.kravatte.end_string_with_suffix(0x0a | self.e << 2);
+    }
+
+    fn keystream(&self, mut history: Kravatte, tag: &[u8]) -> Kravatte {
+        history.update(tag);
+        // the string is followed by the bits `1`, `1` and `e`
+        history.end_string_with_suffix(0x0b | self.e << 2);
+        history
+    }
+}
@@ -41,6 +41,9 @@
 #![no_std]
 #![deny(missing_docs)]
 
+#[cfg(any(feature = "parallel", test))]
+extern crate std;
+
 const RHO: [u32; 24] = [
     1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
 ];
@@ -51,81 +54,250 @@ const PI: [usize; 24] = [
 
 const WORDS: usize = 25;
 
+const MAX_ROUNDS: usize = 24;
+
+/// Computes the round constant of round `round` of `keccak-f[1600]` with the `rc(t)` LFSR
+/// defined in [`FIPS-202`].
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+const fn round_constant(round: usize) -> u64 {
+    let mut lfsr: u8 = 1;
+    let mut rc = 0u64;
+    let mut t = 0;
+    while t < 7 * (round + 1) {
+        if t >= 7 * round && lfsr & 1 == 1 {
+            rc |= 1 << ((1 << (t - 7 * round)) - 1);
+        }
+        lfsr = if lfsr & 0x80 != 0 {
+            (lfsr << 1) ^ 0x71
+        } else {
+            lfsr << 1
+        };
+        t += 1;
+    }
+    rc
+}
+
+/// Returns the last `ROUNDS` round constants of the 24-round `keccak-f[1600]` schedule.
+const fn round_constants<const ROUNDS: usize>() -> [u64; ROUNDS] {
+    let mut rc = [0u64; ROUNDS];
+    let mut i = 0;
+    while i < ROUNDS {
+        rc[i] = round_constant(MAX_ROUNDS - ROUNDS + i);
+        i += 1;
+    }
+    rc
+}
+
+const RC: [u64; MAX_ROUNDS] = round_constants::<MAX_ROUNDS>();
+
+#[cfg(any(feature = "keccakf200", feature = "keccakf400", feature = "keccakf800"))]
+macro_rules! truncated_round_constants {
+    ($lane: ty, $rounds: expr) => {{
+        let mut rc: [$lane; $rounds] = [0; $rounds];
+        let mut i = 0;
+        while i < $rounds {
+            rc[i] = $crate::round_constant(i) as $lane;
+            i += 1;
+        }
+        rc
+    }};
+}
+
 macro_rules! keccak_function {
     ($doc: expr, $name: ident, $rounds: expr, $rc: expr) => {
+        keccak_function!($doc, $name, u64, $rounds, $rc);
+    };
+
+    ($doc: expr, $name: ident, $lane: ty, $rounds: expr, $rc: expr) => {
         #[doc = $doc]
+        #[cfg(not(feature = "small"))]
         #[allow(unused_assignments)]
         #[allow(non_upper_case_globals)]
-        pub fn $name(a: &mut [u64; $crate::WORDS]) {
-            use crunchy::unroll;
-
-            for i in 0..$rounds {
-                let mut array: [u64; 5] = [0; 5];
-
-                // Theta
-                unroll! {
-                    for x in 0..5 {
-                        unroll! {
-                            for y_count in 0..5 {
-                                let y = y_count * 5;
-                                array[x] ^= a[x + y];
-                            }
+        pub fn $name(a: &mut [$lane; $crate::WORDS]) {
+            keccak_function!(@rounds a, $lane, $rounds, $rc);
+        }
+
+        #[doc = $doc]
+        #[cfg(feature = "small")]
+        pub fn $name(a: &mut [$lane; $crate::WORDS]) {
+            $crate::small::keccak_rounds(a, &$rc);
+        }
+    };
+
+    (@rounds $a: ident, $lane: ty, $rounds: expr, $rc: expr) => {
+        use crunchy::unroll;
+
+        for i in 0..$rounds {
+            let mut array: [$lane; 5] = [0; 5];
+
+            // Theta
+            unroll! {
+                for x in 0..5 {
+                    unroll! {
+                        for y_count in 0..5 {
+                            let y = y_count * 5;
+                            array[x] ^= $a[x + y];
                         }
                     }
                 }
+            }
 
-                unroll! {
-                    for x in 0..5 {
-                        unroll! {
-                            for y_count in 0..5 {
-                                let y = y_count * 5;
-                                a[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
-                            }
+            unroll! {
+                for x in 0..5 {
+                    unroll! {
+                        for y_count in 0..5 {
+                            let y = y_count * 5;
+                            $a[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
                         }
                     }
                 }
+            }
 
-                // Rho and pi
-                let mut last = a[1];
-                unroll! {
-                    for x in 0..24 {
-                        array[0] = a[$crate::PI[x]];
-                        a[$crate::PI[x]] = last.rotate_left($crate::RHO[x]);
-                        last = array[0];
-                    }
+            // Rho and pi
+            let mut last = $a[1];
+            unroll! {
+                for x in 0..24 {
+                    array[0] = $a[$crate::PI[x]];
+                    $a[$crate::PI[x]] = last.rotate_left($crate::RHO[x] % <$lane>::BITS);
+                    last = array[0];
                 }
+            }
 
-                // Chi
-                unroll! {
-                    for y_step in 0..5 {
-                        let y = y_step * 5;
+            // Chi
+            unroll! {
+                for y_step in 0..5 {
+                    let y = y_step * 5;
 
-                        unroll! {
-                            for x in 0..5 {
-                                array[x] = a[y + x];
-                            }
+                    unroll! {
+                        for x in 0..5 {
+                            array[x] = $a[y + x];
                         }
+                    }
 
-                        unroll! {
-                            for x in 0..5 {
-                                a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
-                            }
+                    unroll! {
+                        for x in 0..5 {
+                            $a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
                         }
                     }
-                };
+                }
+            };
 
-                // Iota
-                a[0] ^= $rc[i];
-            }
+            // Iota
+            $a[0] ^= $rc[i];
         }
+    };
+}
+
+/// `keccak-p[1600, n_r]` with an arbitrary number of rounds `n_r`.
+///
+/// As specified in [`FIPS-202`], the last `rounds` rounds of the 24-round `keccak-f[1600]`
+/// schedule are applied, so `keccak_p(state, 24)` is [`keccakf`] and `keccak_p(state, 12)` is
+/// [`keccakp`].
+///
+/// # Panics
+///
+/// Panics if `rounds` is not in range `1..=24`.
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+/// [`keccakf`]: fn.keccakf.html
+/// [`keccakp`]: fn.keccakp.html
+pub fn keccak_p(a: &mut [u64; WORDS], rounds: usize) {
+    assert!(
+        (1..=MAX_ROUNDS).contains(&rounds),
+        "number of rounds must be in range 1..=24"
+    );
+    keccak_rounds(a, &RC[MAX_ROUNDS - rounds..]);
+}
+
+/// Applies one round of `keccak-p[1600]` for every round constant in `rc`.
+#[allow(unused_assignments)]
+fn keccak_rounds(a: &mut [u64; WORDS], rc: &[u64]) {
+    #[cfg(not(feature = "small"))]
+    {
+        keccak_function!(@rounds a, u64, rc.len(), rc);
     }
+    #[cfg(feature = "small")]
+    small::keccak_rounds(a, rc);
 }
 
-#[cfg(feature = "k12")]
+/// Rejects a number of rounds known at compile time outside of `1..=24` when it is evaluated.
+pub(crate) struct CheckRounds<const ROUNDS: usize>;
+
+impl<const ROUNDS: usize> CheckRounds<ROUNDS> {
+    pub(crate) const OK: () = assert!(
+        ROUNDS >= 1 && ROUNDS <= MAX_ROUNDS,
+        "number of rounds must be in range 1..=24"
+    );
+}
+
+/// `keccak-p[1600, ROUNDS]` with the number of rounds known at compile time.
+///
+/// See [`keccak_p`] for details. A `ROUNDS` outside of range `1..=24` fails to compile:
+///
+/// ```compile_fail
+/// let mut state = [0u64; 25];
+/// tiny_keccak::keccak_p_rounds::<25>(&mut state);
+/// ```
+///
+/// [`keccak_p`]: fn.keccak_p.html
+#[inline]
+pub fn keccak_p_rounds<const ROUNDS: usize>(a: &mut [u64; WORDS]) {
+    let () = CheckRounds::<ROUNDS>::OK;
+    keccak_p(a, ROUNDS);
+}
+
+#[cfg(feature = "small")]
+mod small;
+
+mod inverse;
+mod resumable;
+mod steps;
+mod times;
+
+pub use inverse::{keccak_p_inverse, keccak_p_rounds_inverse};
+pub use resumable::ResumableKeccak;
+pub use steps::{chi, iota, keccak_p_traced, pi, rho, theta, Step, Trace};
+
+pub use times::{
+    keccakf_times2, keccakf_times4, keccakf_times8, keccakp_times2, keccakp_times4, keccakp_times8,
+};
+
+#[cfg(any(feature = "k12", feature = "turbo_shake"))]
 mod keccakp;
 
-#[cfg(feature = "k12")]
-pub use keccakp::keccakp;
+#[cfg(any(feature = "k12", feature = "turbo_shake"))]
+pub use keccakp::{keccakp, keccakp_inverse, KeccakP};
+
+#[cfg(feature = "m14")]
+mod keccakp14;
+
+#[cfg(feature = "m14")]
+pub use keccakp14::{keccakp14, KeccakP14};
+
+#[cfg(feature = "kravatte")]
+mod keccakp6;
+
+#[cfg(feature = "kravatte")]
+pub use keccakp6::{keccakp6, KeccakP6};
+
+#[cfg(feature = "keccakf200")]
+mod keccakf200;
+
+#[cfg(feature = "keccakf200")]
+pub use keccakf200::{keccakf200, KeccakF200};
+
+#[cfg(feature = "keccakf400")]
+mod keccakf400;
+
+#[cfg(feature = "keccakf400")]
+pub use keccakf400::{keccakf400, KeccakF400};
+
+#[cfg(feature = "keccakf800")]
+mod keccakf800;
+
+#[cfg(feature = "keccakf800")]
+pub use keccakf800::{keccakf800, KeccakF800};
 
 #[cfg(any(
     feature = "keccak",
@@ -134,7 +306,9 @@ pub use keccakp::keccakp;
     feature = "cshake",
     feature = "kmac",
     feature = "tuple_hash",
-    feature = "parallel_hash"
+    feature = "parallel_hash",
+    feature = "sponge_wrap",
+    feature = "strobe"
 ))]
 mod keccakf;
 
@@ -145,15 +319,118 @@ mod keccakf;
     feature = "cshake",
     feature = "kmac",
     feature = "tuple_hash",
-    feature = "parallel_hash"
+    feature = "parallel_hash",
+    feature = "sponge_wrap",
+    feature = "strobe"
+))]
+pub use keccakf::{keccakf, keccakf_inverse, KeccakF};
+
+#[cfg(any(
+    feature = "keccak",
+    feature = "shake",
+    feature = "sha3",
+    feature = "cshake",
+    feature = "kmac",
+    feature = "tuple_hash",
+    feature = "parallel_hash",
+    feature = "sponge_wrap"
+))]
+#[cfg_attr(
+    any(not(target_pointer_width = "32"), feature = "small"),
+    allow(dead_code)
+)]
+mod keccakf_interleaved;
+
+#[cfg(feature = "lane_complementing")]
+#[cfg_attr(feature = "small", allow(dead_code))]
+mod keccakf_complementing;
+
+#[cfg(feature = "lane_complementing")]
+pub use keccakf_complementing::keccakf_complementing;
+
+/// Selects the `keccak-f[1600, 24]` implementation driving the sponges of this crate.
+#[cfg(any(
+    feature = "keccak",
+    feature = "shake",
+    feature = "sha3",
+    feature = "cshake",
+    feature = "kmac",
+    feature = "tuple_hash",
+    feature = "parallel_hash",
+    feature = "sponge_wrap"
 ))]
-pub use keccakf::keccakf;
+mod native {
+    /// The bit-interleaved permutation on 32-bit targets.
+    #[cfg(all(target_pointer_width = "32", not(feature = "small")))]
+    pub(crate) type KeccakFNative = crate::keccakf_interleaved::KeccakFInterleaved;
+
+    /// The lane-complementing permutation with the `lane_complementing` feature.
+    #[cfg(all(
+        not(target_pointer_width = "32"),
+        feature = "lane_complementing",
+        not(feature = "small")
+    ))]
+    pub(crate) type KeccakFNative = crate::keccakf_complementing::KeccakFComplementing;
+
+    /// [`KeccakF`] everywhere else, and with the `small` feature, which favours the looped
+    /// permutation shared with the other instances over another unrolled one.
+    ///
+    /// [`KeccakF`]: ../struct.KeccakF.html
+    #[cfg(any(
+        feature = "small",
+        all(not(target_pointer_width = "32"), not(feature = "lane_complementing"))
+    ))]
+    pub(crate) type KeccakFNative = crate::keccakf::KeccakF;
+
+    /// [`KeccakFNative`] computed twice with the `fault_detection` feature.
+    ///
+    /// [`KeccakFNative`]: type.KeccakFNative.html
+    #[cfg(all(feature = "fault_detection", any(feature = "sha3", feature = "cshake")))]
+    pub(crate) type KeccakFChecked = crate::Redundant<KeccakFNative>;
+
+    /// [`KeccakFNative`] without the `fault_detection` feature.
+    ///
+    /// [`KeccakFNative`]: type.KeccakFNative.html
+    #[cfg(all(
+        not(feature = "fault_detection"),
+        any(feature = "sha3", feature = "cshake")
+    ))]
+    pub(crate) type KeccakFChecked = KeccakFNative;
+
+    /// [`KeccakF`] computed twice with the `fault_detection` feature, for the multi-state
+    /// permutations of the batch functions, which always work on `u64` lanes.
+    ///
+    /// [`KeccakF`]: ../struct.KeccakF.html
+    #[cfg(all(feature = "fault_detection", feature = "sha3"))]
+    pub(crate) type KeccakFTimesChecked = crate::Redundant<crate::keccakf::KeccakF>;
+
+    /// [`KeccakF`] without the `fault_detection` feature.
+    ///
+    /// [`KeccakF`]: ../struct.KeccakF.html
+    #[cfg(all(not(feature = "fault_detection"), feature = "sha3"))]
+    pub(crate) type KeccakFTimesChecked = crate::keccakf::KeccakF;
+}
+
+#[cfg(any(feature = "keccak", feature = "shake", feature = "sponge_wrap"))]
+use native::KeccakFNative;
+
+#[cfg(any(feature = "sha3", feature = "cshake"))]
+use native::KeccakFChecked;
+
+#[cfg(feature = "sha3")]
+use native::KeccakFTimesChecked;
 
 #[cfg(feature = "k12")]
 mod k12;
 
 #[cfg(feature = "k12")]
-pub use k12::{KangarooTwelve, KangarooTwelveXof};
+pub use k12::{KangarooTwelve, KangarooTwelve256, KangarooTwelve256Xof, KangarooTwelveXof};
+
+#[cfg(feature = "m14")]
+mod m14;
+
+#[cfg(feature = "m14")]
+pub use m14::{MarsupilamiFourteen, MarsupilamiFourteenXof};
 
 #[cfg(feature = "keccak")]
 mod keccak;
@@ -173,6 +450,12 @@ mod sha3;
 #[cfg(feature = "sha3")]
 pub use sha3::Sha3;
 
+#[cfg(feature = "turbo_shake")]
+mod turbo_shake;
+
+#[cfg(feature = "turbo_shake")]
+pub use turbo_shake::TurboShake;
+
 #[cfg(feature = "cshake")]
 mod cshake;
 
@@ -191,12 +474,90 @@ mod tuple_hash;
 #[cfg(feature = "tuple_hash")]
 pub use tuple_hash::{TupleHash, TupleHashXof};
 
+#[cfg(feature = "transcript")]
+mod transcript;
+
+#[cfg(feature = "transcript")]
+pub use transcript::Transcript;
+
 #[cfg(feature = "parallel_hash")]
 mod parallel_hash;
 
 #[cfg(feature = "parallel_hash")]
 pub use parallel_hash::{ParallelHash, ParallelHashXof};
 
+#[cfg(feature = "masked")]
+mod masked;
+
+#[cfg(feature = "masked")]
+pub use masked::{keccakf_masked, mask, unmask, MaskedKmac, MaskedKmacXof, MaskedShake, Rng};
+
+#[cfg(feature = "duplex")]
+mod duplex;
+
+#[cfg(feature = "duplex")]
+pub use duplex::Duplex;
+
+#[cfg(feature = "farfalle")]
+mod farfalle;
+
+#[cfg(feature = "farfalle")]
+pub use farfalle::{Farfalle, Rolling};
+
+#[cfg(feature = "sponge")]
+mod sponge;
+
+#[cfg(feature = "sponge")]
+pub use sponge::Sponge;
+
+#[cfg(feature = "sponge_wrap")]
+mod sponge_wrap;
+
+#[cfg(feature = "sponge_wrap")]
+pub use sponge_wrap::SpongeWrap;
+
+#[cfg(feature = "kravatte")]
+mod kravatte;
+
+#[cfg(feature = "kravatte")]
+pub use kravatte::{Kravatte, KravatteRolling, KravatteSane, KravatteSanse};
+
+#[cfg(feature = "strobe")]
+mod strobe;
+
+#[cfg(feature = "strobe")]
+pub use strobe::Strobe;
+
+/// The error returned when an authentication tag does not match.
+#[cfg(any(feature = "sponge_wrap", feature = "kravatte", feature = "strobe"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTag;
+
+#[cfg(any(feature = "sponge_wrap", feature = "kravatte", feature = "strobe"))]
+impl core::fmt::Display for InvalidTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("invalid authentication tag")
+    }
+}
+
+/// The error returned when a redundant computation of the permutation did not match.
+#[cfg(feature = "fault_detection")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultDetected;
+
+#[cfg(feature = "fault_detection")]
+impl core::fmt::Display for FaultDetected {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("fault detected in the permutation")
+    }
+}
+
+#[cfg(feature = "fault_detection")]
+mod redundant;
+
+#[cfg(feature = "fault_detection")]
+pub use redundant::Redundant;
+
 /// A trait for hashing an arbitrary stream of bytes.
 ///
 /// # Example
@@ -266,17 +627,20 @@ pub trait Xof {
     fn squeeze(&mut self, output: &mut [u8]);
 }
 
+#[cfg(any(feature = "cshake", feature = "k12"))]
 struct EncodedLen {
     offset: usize,
     buffer: [u8; 9],
 }
 
+#[cfg(any(feature = "cshake", feature = "k12"))]
 impl EncodedLen {
     fn value(&self) -> &[u8] {
         &self.buffer[self.offset..]
     }
 }
 
+#[cfg(feature = "cshake")]
 fn left_encode(len: usize) -> EncodedLen {
     let mut buffer = [0u8; 9];
     buffer[1..].copy_from_slice(&(len as u64).to_be_bytes());
@@ -289,6 +653,12 @@ fn left_encode(len: usize) -> EncodedLen {
     }
 }
 
+#[cfg(any(
+    feature = "kmac",
+    feature = "masked",
+    feature = "parallel_hash",
+    feature = "tuple_hash"
+))]
 fn right_encode(len: usize) -> EncodedLen {
     let mut buffer = [0u8; 9];
     buffer[..8].copy_from_slice(&(len as u64).to_be_bytes());
@@ -297,66 +667,227 @@ fn right_encode(len: usize) -> EncodedLen {
     EncodedLen { offset, buffer }
 }
 
-#[derive(Default, Clone)]
-struct Buffer([u64; WORDS]);
+mod sealed {
+    pub trait Sealed: Sized {
+        /// Converts the lane `index` of the state from the representation the permutation
+        /// works on into the little-endian one its bytes are absorbed and squeezed in.
+        fn to_le_lane(self, index: usize) -> Self;
+
+        /// Converts the lane `index` of the state from its little-endian representation back
+        /// into the one the permutation works on.
+        fn to_native_lane(self, index: usize) -> Self;
 
-impl Buffer {
-    fn words(&mut self) -> &mut [u64; WORDS] {
+        /// Xors the little-endian `bytes`, exactly one lane long, into the lane `index` of the
+        /// state.
+        fn xor_le_bytes(self, bytes: &[u8], index: usize) -> Self;
+
+        /// Stores the lane `index` of the state into `bytes`, exactly one lane long, as
+        /// little-endian.
+        fn write_le_bytes(self, bytes: &mut [u8], index: usize);
+    }
+}
+
+/// A lane of the `keccak` state: `u8`, `u16`, `u32` or `u64`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Lane: sealed::Sealed + Copy + Default + PartialEq {}
+
+macro_rules! impl_lane {
+    ($($lane: ty),*) => {
+        $(
+            impl sealed::Sealed for $lane {
+                #[inline]
+                fn to_le_lane(self, _index: usize) -> Self {
+                    <$lane>::to_le(self)
+                }
+
+                #[inline]
+                fn to_native_lane(self, _index: usize) -> Self {
+                    <$lane>::from_le(self)
+                }
+
+                #[inline]
+                fn xor_le_bytes(self, bytes: &[u8], _index: usize) -> Self {
+                    let mut le = [0u8; core::mem::size_of::<$lane>()];
+                    le.copy_from_slice(bytes);
+                    self ^ <$lane>::from_le_bytes(le)
+                }
+
+                #[inline]
+                fn write_le_bytes(self, bytes: &mut [u8], _index: usize) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl Lane for $lane {}
+        )*
+    };
+}
+
+impl_lane!(u8, u16, u32, u64);
+
+#[derive(Clone)]
+struct Buffer<L = u64>([L; WORDS]);
+
+impl<L: Lane> Default for Buffer<L> {
+    /// The zero state, in the representation the permutation works on.
+    fn default() -> Self {
+        let mut lanes = [L::default(); WORDS];
+        for (i, lane) in lanes.iter_mut().enumerate() {
+            *lane = lane.to_native_lane(i);
+        }
+        Buffer(lanes)
+    }
+}
+
+impl<L: Lane> Buffer<L> {
+    const LANE_BYTES: usize = core::mem::size_of::<L>();
+
+    fn words(&mut self) -> &mut [L; WORDS] {
         &mut self.0
     }
 
-    #[cfg(target_endian = "little")]
+    #[cfg(any(feature = "farfalle", feature = "strobe", test))]
     #[inline]
-    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
-        let buffer: &mut [u8; WORDS * 8] = unsafe { core::mem::transmute(&mut self.0) };
-        f(&mut buffer[offset..][..len]);
+    fn bytes(&mut self) -> &mut [u8] {
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                self.0.as_mut_ptr() as *mut u8,
+                WORDS * Self::LANE_BYTES,
+            )
+        }
     }
 
-    #[cfg(target_endian = "big")]
+    /// Runs `f` on the bytes `offset..offset + len` of the state, converting the lanes they
+    /// belong to into their little-endian representation for the duration of the call. This
+    /// is a no-op for integer lanes on little-endian targets.
+    ///
+    /// Only the duplex constructions which modify the state in place need it; [`xorin`],
+    /// [`setout`] and [`pad`] convert just the input and output lanes instead.
+    ///
+    /// [`xorin`]: #method.xorin
+    /// [`setout`]: #method.setout
+    /// [`pad`]: #method.pad
+    #[cfg(any(feature = "farfalle", feature = "strobe", test))]
     #[inline]
     fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
-        fn swap_endianess(buffer: &mut [u64]) {
-            for item in buffer {
-                *item = item.swap_bytes();
-            }
+        let start = offset / Self::LANE_BYTES;
+        let end = (offset + len).div_ceil(Self::LANE_BYTES);
+        for (i, lane) in self.0.iter_mut().enumerate().take(end).skip(start) {
+            *lane = lane.to_le_lane(i);
         }
+        f(&mut self.bytes()[offset..][..len]);
+        for (i, lane) in self.0.iter_mut().enumerate().take(end).skip(start) {
+            *lane = lane.to_native_lane(i);
+        }
+    }
 
-        let start = offset / 8;
-        let end = (offset + len + 7) / 8;
-        swap_endianess(&mut self.0[start..end]);
-        let buffer: &mut [u8; WORDS * 8] = unsafe { core::mem::transmute(&mut self.0) };
-        f(&mut buffer[offset..][..len]);
-        swap_endianess(&mut self.0[start..end]);
+    /// Splits the bytes `offset..offset + len` of the state into the bytes before the first
+    /// whole lane, the whole lanes, and the bytes after them, returning the length of the first
+    /// part and the number of whole lanes.
+    #[inline]
+    fn split_lanes(offset: usize, len: usize) -> (usize, usize) {
+        let head = core::cmp::min(offset.wrapping_neg() % Self::LANE_BYTES, len);
+        (head, (len - head) / Self::LANE_BYTES)
     }
 
     fn setout(&mut self, dst: &mut [u8], offset: usize, len: usize) {
-        self.execute(offset, len, |buffer| dst[..len].copy_from_slice(buffer));
+        let dst = &mut dst[..len];
+        let (head, lanes) = Self::split_lanes(offset, len);
+        let (head_dst, dst) = dst.split_at_mut(head);
+        let (lanes_dst, tail_dst) = dst.split_at_mut(lanes * Self::LANE_BYTES);
+
+        self.setout_bytes(head_dst, offset);
+        let first = (offset + head) / Self::LANE_BYTES;
+        for (i, bytes) in lanes_dst.chunks_exact_mut(Self::LANE_BYTES).enumerate() {
+            self.0[first + i].write_le_bytes(bytes, first + i);
+        }
+        self.setout_bytes(tail_dst, (first + lanes) * Self::LANE_BYTES);
     }
 
     fn xorin(&mut self, src: &[u8], offset: usize, len: usize) {
-        self.execute(offset, len, |dst| {
-            assert!(dst.len() <= src.len());
-            let len = dst.len();
-            let mut dst_ptr = dst.as_mut_ptr();
-            let mut src_ptr = src.as_ptr();
-            for _ in 0..len {
-                unsafe {
-                    *dst_ptr ^= *src_ptr;
-                    src_ptr = src_ptr.offset(1);
-                    dst_ptr = dst_ptr.offset(1);
-                }
-            }
-        });
+        let src = &src[..len];
+        let (head, lanes) = Self::split_lanes(offset, len);
+        let (head_src, src) = src.split_at(head);
+        let (lanes_src, tail_src) = src.split_at(lanes * Self::LANE_BYTES);
+
+        self.xorin_bytes(head_src, offset);
+        let first = (offset + head) / Self::LANE_BYTES;
+        for (i, bytes) in lanes_src.chunks_exact(Self::LANE_BYTES).enumerate() {
+            self.0[first + i] = self.0[first + i].xor_le_bytes(bytes, first + i);
+        }
+        self.xorin_bytes(tail_src, (first + lanes) * Self::LANE_BYTES);
+    }
+
+    /// Copies the bytes of the state from `offset` on into `dst`, which must not cross a lane
+    /// boundary, by storing the whole lane and copying the requested part.
+    fn setout_bytes(&mut self, dst: &mut [u8], offset: usize) {
+        if dst.is_empty() {
+            return;
+        }
+        let (index, start) = (offset / Self::LANE_BYTES, offset % Self::LANE_BYTES);
+        let mut le = [0u8; 8];
+        self.0[index].write_le_bytes(&mut le[..Self::LANE_BYTES], index);
+        dst.copy_from_slice(&le[start..][..dst.len()]);
+    }
+
+    /// Xors `src` into the bytes of the state from `offset` on, which must not cross a lane
+    /// boundary, by xoring in a whole lane which is zero outside of them.
+    fn xorin_bytes(&mut self, src: &[u8], offset: usize) {
+        if src.is_empty() {
+            return;
+        }
+        let (index, start) = (offset / Self::LANE_BYTES, offset % Self::LANE_BYTES);
+        let mut le = [0u8; 8];
+        le[start..][..src.len()].copy_from_slice(src);
+        self.0[index] = self.0[index].xor_le_bytes(&le[..Self::LANE_BYTES], index);
     }
 
     fn pad(&mut self, offset: usize, delim: u8, rate: usize) {
-        self.execute(offset, 1, |buff| buff[0] ^= delim);
-        self.execute(rate - 1, 1, |buff| buff[0] ^= 0x80);
+        self.xorin_bytes(&[delim], offset);
+        self.xorin_bytes(&[0x80], rate - 1);
     }
 }
 
-trait Permutation {
-    fn execute(a: &mut Buffer);
+/// A permutation of the 25-lane `keccak` state.
+///
+/// Implement this trait to plug a custom permutation, e.g. a hardware accelerator or a masked
+/// implementation, into [`Sponge`].
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{keccak_p, Permutation};
+/// struct KeccakP6;
+///
+/// impl Permutation for KeccakP6 {
+///     type Lane = u64;
+///
+///     fn execute(state: &mut [u64; 25]) {
+///         keccak_p(state, 6);
+///     }
+/// }
+/// ```
+///
+/// [`Sponge`]: struct.Sponge.html
+pub trait Permutation {
+    /// The type of a single lane of the state.
+    type Lane: Lane;
+
+    /// Applies the permutation to the state.
+    fn execute(state: &mut [Self::Lane; WORDS]);
+
+    /// Applies the permutation to the state, reporting a fault if one was detected.
+    ///
+    /// Only permutations which check their own computation, like [`Redundant`], can detect
+    /// faults; the default implementation never does.
+    ///
+    /// [`Redundant`]: struct.Redundant.html
+    #[cfg(feature = "fault_detection")]
+    fn execute_checked(state: &mut [Self::Lane; WORDS]) -> Result<(), FaultDetected> {
+        Self::execute(state);
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -365,16 +896,22 @@ enum Mode {
     Squeezing,
 }
 
-struct KeccakState<P> {
-    buffer: Buffer,
+struct KeccakState<P: Permutation> {
+    buffer: Buffer<P::Lane>,
     offset: usize,
     rate: usize,
     delim: u8,
     mode: Mode,
+    #[cfg(feature = "fault_detection")]
+    fault: bool,
+    /// The next round of the permutation in progress in a stepped operation.
+    round: Option<usize>,
+    /// The bytes of the input or output of a stepped operation processed so far.
+    progress: usize,
     permutation: core::marker::PhantomData<P>,
 }
 
-impl<P> Clone for KeccakState<P> {
+impl<P: Permutation> Clone for KeccakState<P> {
     fn clone(&self) -> Self {
         KeccakState {
             buffer: self.buffer.clone(),
@@ -382,29 +919,62 @@ impl<P> Clone for KeccakState<P> {
             rate: self.rate,
             delim: self.delim,
             mode: self.mode,
+            #[cfg(feature = "fault_detection")]
+            fault: self.fault,
+            round: self.round,
+            progress: self.progress,
             permutation: core::marker::PhantomData,
         }
     }
 }
 
+// without a hasher or `Sponge`, only the `Buffer` is used
+#[cfg_attr(
+    not(any(
+        feature = "cshake",
+        feature = "k12",
+        feature = "keccak",
+        feature = "sha3",
+        feature = "shake",
+        feature = "sponge",
+        feature = "turbo_shake"
+    )),
+    allow(dead_code)
+)]
 impl<P: Permutation> KeccakState<P> {
     fn new(rate: usize, delim: u8) -> Self {
         assert!(rate != 0, "rate cannot be equal 0");
+        assert!(
+            rate <= WORDS * Buffer::<P::Lane>::LANE_BYTES,
+            "rate cannot be greater than the state size"
+        );
         KeccakState {
             buffer: Buffer::default(),
             offset: 0,
             rate,
             delim,
             mode: Mode::Absorbing,
+            #[cfg(feature = "fault_detection")]
+            fault: false,
+            round: None,
+            progress: 0,
             permutation: core::marker::PhantomData,
         }
     }
 
+    #[cfg(not(feature = "fault_detection"))]
     fn keccak(&mut self) {
-        P::execute(&mut self.buffer);
+        P::execute(self.buffer.words());
+    }
+
+    #[cfg(feature = "fault_detection")]
+    fn keccak(&mut self) {
+        // the fault sticks until the state is reset
+        self.fault |= P::execute_checked(self.buffer.words()).is_err();
     }
 
     fn update(&mut self, input: &[u8]) {
+        assert!(self.round.is_none(), "a stepped operation is still pending");
         if let Mode::Squeezing = self.mode {
             self.mode = Mode::Absorbing;
             self.fill_block();
@@ -432,12 +1002,61 @@ impl<P: Permutation> KeccakState<P> {
         self.buffer.pad(self.offset, self.delim, self.rate);
     }
 
-    fn squeeze(&mut self, output: &mut [u8]) {
+    fn switch_to_squeezing(&mut self) {
         if let Mode::Absorbing = self.mode {
             self.mode = Mode::Squeezing;
             self.pad();
             self.fill_block();
         }
+    }
+
+    #[cfg(not(feature = "fault_detection"))]
+    fn squeeze(&mut self, output: &mut [u8]) {
+        self.squeeze_unchecked(output);
+    }
+
+    #[cfg(feature = "fault_detection")]
+    fn squeeze(&mut self, output: &mut [u8]) {
+        if let Err(err) = self.try_squeeze(output) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Squeezes the output, unless a fault was detected in any permutation of the state so far.
+    ///
+    /// No byte of a state in which a fault was detected is ever written: a fault detected
+    /// before the call leaves `output` untouched, and one detected while squeezing zeroes the
+    /// part of `output` written before it.
+    #[cfg(feature = "fault_detection")]
+    fn try_squeeze(&mut self, output: &mut [u8]) -> Result<(), FaultDetected> {
+        if self.fault {
+            return Err(FaultDetected);
+        }
+        let written = self.squeeze_unchecked(output);
+        if self.fault {
+            output[..written].iter_mut().for_each(|byte| *byte = 0);
+            return Err(FaultDetected);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "fault_detection"))]
+    #[inline(always)]
+    fn faulted(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "fault_detection")]
+    #[inline(always)]
+    fn faulted(&self) -> bool {
+        self.fault
+    }
+
+    /// Squeezes the output, stopping before the first block of a faulted state. Returns the
+    /// number of bytes written.
+    fn squeeze_unchecked(&mut self, output: &mut [u8]) -> usize {
+        assert!(self.round.is_none(), "a stepped operation is still pending");
+        self.switch_to_squeezing();
 
         // second foldp
         let mut op = 0;
@@ -445,6 +1064,9 @@ impl<P: Permutation> KeccakState<P> {
         let mut rate = self.rate - self.offset;
         let mut offset = self.offset;
         while l >= rate {
+            if self.faulted() {
+                return op;
+            }
             self.buffer.setout(&mut output[op..], offset, rate);
             self.keccak();
             op += rate;
@@ -453,34 +1075,79 @@ impl<P: Permutation> KeccakState<P> {
             offset = 0;
         }
 
+        if self.faulted() {
+            return op;
+        }
         self.buffer.setout(&mut output[op..], offset, l);
         self.offset = offset + l;
+        op + l
     }
 
+    #[cfg(any(
+        feature = "cshake",
+        feature = "k12",
+        feature = "keccak",
+        feature = "sha3",
+        feature = "shake",
+        feature = "turbo_shake"
+    ))]
     fn finalize(mut self, output: &mut [u8]) {
         self.squeeze(output);
     }
 
+    #[cfg(all(feature = "fault_detection", any(feature = "sha3", feature = "kmac")))]
+    fn try_finalize(mut self, output: &mut [u8]) -> Result<(), FaultDetected> {
+        self.try_squeeze(output)
+    }
+
     fn fill_block(&mut self) {
         self.keccak();
         self.offset = 0;
     }
 
+    #[cfg_attr(not(feature = "sponge"), allow(dead_code))]
     fn reset(&mut self) {
         self.buffer = Buffer::default();
         self.offset = 0;
         self.mode = Mode::Absorbing;
+        self.round = None;
+        self.progress = 0;
+        #[cfg(feature = "fault_detection")]
+        {
+            self.fault = false;
+        }
     }
 }
 
+#[cfg(any(
+    feature = "cshake",
+    feature = "k12",
+    feature = "keccak",
+    feature = "kmac",
+    feature = "masked",
+    feature = "parallel_hash",
+    feature = "sha3",
+    feature = "shake",
+    feature = "sponge_wrap",
+    feature = "turbo_shake"
+))]
 fn bits_to_rate(bits: usize) -> usize {
     200 - bits / 4
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{left_encode, right_encode};
-
+    #[cfg(feature = "cshake")]
+    use crate::left_encode;
+    #[cfg(any(
+        feature = "kmac",
+        feature = "masked",
+        feature = "parallel_hash",
+        feature = "tuple_hash"
+    ))]
+    use crate::right_encode;
+
+    #[cfg(feature = "cshake")]
     #[test]
     fn test_left_encode() {
         assert_eq!(left_encode(0).value(), &[1, 0]);
@@ -490,6 +1157,12 @@ mod tests {
         assert_eq!(left_encode(54321).value(), &[2, 212, 49]);
     }
 
+    #[cfg(any(
+        feature = "kmac",
+        feature = "masked",
+        feature = "parallel_hash",
+        feature = "tuple_hash"
+    ))]
     #[test]
     fn test_right_encode() {
         assert_eq!(right_encode(0).value(), &[0, 1]);
@@ -498,4 +1171,460 @@ mod tests {
         assert_eq!(right_encode(4096).value(), &[16, 0, 2]);
         assert_eq!(right_encode(54321).value(), &[212, 49, 2]);
     }
+
+    fn test_keccak_times_portable<const N: usize>(permutation: fn(&mut [[u64; N]; crate::WORDS])) {
+        use crate::times::keccak_portable;
+
+        let mut a = [[0u64; N]; crate::WORDS];
+        for (i, lanes) in a.iter_mut().enumerate() {
+            for (j, lane) in lanes.iter_mut().enumerate() {
+                *lane = (0xfedcba9876543210 ^ i as u64).rotate_left(7 * j as u32);
+            }
+        }
+        let mut b = a;
+        permutation(&mut a);
+        keccak_portable(&mut b, &crate::RC);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_keccak_times_matches_portable() {
+        // on `x86_64` with `AVX2` this compares the vectorized implementation
+        test_keccak_times_portable(crate::keccakf_times2);
+        test_keccak_times_portable(crate::keccakf_times4);
+        test_keccak_times_portable(crate::keccakf_times8);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn test_avx2_portable<const N: usize>(rounds: usize) {
+        use crate::times::{avx2, keccak_portable};
+
+        let rc = &crate::RC[crate::MAX_ROUNDS - rounds..];
+        let mut a = [[0u64; N]; crate::WORDS];
+        for (i, lanes) in a.iter_mut().enumerate() {
+            for (j, lane) in lanes.iter_mut().enumerate() {
+                *lane = (0x0123456789abcdef ^ i as u64).rotate_left(5 * j as u32 + 1);
+            }
+        }
+        let mut b = a;
+        for offset in (0..N).step_by(4) {
+            unsafe { avx2::keccak(&mut a, offset, rc) };
+        }
+        keccak_portable(&mut b, rc);
+        assert_eq!(a, b);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_avx2_matches_portable() {
+        if !std::is_x86_feature_detected!("avx2") {
+            return;
+        }
+        test_avx2_portable::<4>(24);
+        test_avx2_portable::<4>(12);
+        test_avx2_portable::<8>(24);
+        test_avx2_portable::<8>(12);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_sponge_batch_rejects_unequal_outputs() {
+        use crate::{times::sponge_batch, KeccakF};
+        use std::{panic, vec};
+
+        let inputs = [[0u8; 10]; 9];
+        let mut outputs = vec![vec![0u8; 32]; 9];
+        outputs[8].pop();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            sponge_batch::<KeccakF, _, _>(136, 0x06, &inputs, &mut outputs);
+        }));
+        assert!(result.is_err());
+        // the first group of 8 outputs was not written either
+        assert!(outputs.iter().flatten().all(|byte| *byte == 0));
+    }
+
+    #[cfg(any(feature = "keccakf200", feature = "keccakf400", feature = "keccakf800"))]
+    fn test_small_width_state<P: crate::Permutation>(rate: usize, input: &[u8], expected: &[u8]) {
+        use crate::KeccakState;
+
+        let mut output = [0u8; 100];
+        let output = &mut output[..expected.len()];
+        let mut state = KeccakState::<P>::new(rate, 0x01);
+        state.update(&input[..7]);
+        state.update(&input[7..]);
+        state.squeeze(&mut output[..3]);
+        state.squeeze(&mut output[3..]);
+        assert_eq!(expected, output);
+    }
+
+    #[cfg(feature = "keccakf200")]
+    #[test]
+    fn test_keccakf200_state() {
+        let input: [u8; 50] = core::array::from_fn(|i| i as u8);
+        let expected = b"\
+            \x0a\xf1\x8d\x6c\x1a\x87\x43\x00\xcd\xea\x1f\xc8\x75\x79\xbc\xb5\
+            \xc2\x98\x54\xd6\x5f\x4b\x77\x29\x03\xda\x49\xa4\xdf\xaf\x90\xf2\
+            \x48\x27\x03\x38\x74\x9a\x58\x6d\
+        ";
+        test_small_width_state::<crate::keccakf200::KeccakF200>(18, &input, expected);
+    }
+
+    #[cfg(feature = "keccakf400")]
+    #[test]
+    fn test_keccakf400_state() {
+        let input: [u8; 50] = core::array::from_fn(|i| i as u8);
+        let expected = b"\
+            \xe8\x3d\x7b\x91\xaa\x7b\x66\xe5\x94\xf4\xce\xcc\x84\x1a\xf1\x98\
+            \x92\x67\x93\xee\xdb\xf2\xb8\x61\x54\x9d\xaf\x37\xd0\xb4\xa8\xa2\
+            \x91\x46\x53\xa9\xdf\x8c\x65\xcf\
+        ";
+        test_small_width_state::<crate::keccakf400::KeccakF400>(34, &input, expected);
+    }
+
+    #[cfg(feature = "keccakf800")]
+    #[test]
+    fn test_keccakf800_state() {
+        let input: [u8; 150] = core::array::from_fn(|i| i as u8);
+        let expected = b"\
+            \x98\x0d\xb2\x19\x24\x8a\xa5\xeb\x86\x28\x90\x3b\xc3\x5a\x00\xd3\
+            \xc6\xc8\x0c\xd5\xbf\xf5\xf6\xdf\xd0\xad\xfa\x13\xa8\x1f\xc7\x46\
+            \x5f\x42\x08\xc1\x2e\x7d\xf1\x43\xfe\x37\x0f\xc7\x2f\xb9\x29\x68\
+            \xb2\xaa\x93\x9c\xc0\xdf\x65\x0d\xb5\x75\x60\xd3\x79\x67\x31\x3c\
+            \x26\xc6\x00\xa9\x09\xcc\x4b\x49\xfd\x33\xf0\x29\x5a\x6f\xa7\xbe\
+            \x3f\x38\x65\xc2\x4c\xf9\x8a\x9b\x2d\x43\x00\x3d\x54\x69\xad\xc1\
+            \x20\x75\x65\x26\
+        ";
+        test_small_width_state::<crate::keccakf800::KeccakF800>(68, &input, expected);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_keccakf_interleaved_matches_keccakf() {
+        use crate::keccakf::keccakf;
+        use crate::keccakf_interleaved::{
+            deinterleave, interleave, keccakf_interleaved, Interleaved,
+        };
+
+        let mut a = [0u64; crate::WORDS];
+        for (i, lane) in a.iter_mut().enumerate() {
+            *lane = (0x0123456789abcdef ^ i as u64).rotate_left(11 * i as u32);
+        }
+        let mut b = [Interleaved::default(); crate::WORDS];
+        for (lane, interleaved) in a.iter().zip(b.iter_mut()) {
+            *interleaved = Interleaved(interleave(*lane));
+            assert_eq!(deinterleave(interleaved.0), *lane);
+        }
+
+        for _ in 0..3 {
+            keccakf(&mut a);
+            keccakf_interleaved(&mut b);
+            for (lane, interleaved) in a.iter().zip(b.iter()) {
+                assert_eq!(*lane, deinterleave(interleaved.0));
+            }
+        }
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_keccakf_interleaved_sponge_matches_keccakf() {
+        use crate::keccakf::KeccakF;
+        use crate::keccakf_interleaved::KeccakFInterleaved;
+        use crate::KeccakState;
+
+        let input: [u8; 500] = core::array::from_fn(|i| (i * 7) as u8);
+        for rate in &[72, 136, 168] {
+            // unaligned pieces exercise the conversion of partially read and written lanes
+            let mut a = KeccakState::<KeccakF>::new(*rate, 0x06);
+            let mut b = KeccakState::<KeccakFInterleaved>::new(*rate, 0x06);
+            for piece in input.chunks(13) {
+                a.update(piece);
+                b.update(piece);
+            }
+
+            let mut expected = [0u8; 400];
+            let mut output = [0u8; 400];
+            for (expected, output) in expected.chunks_mut(37).zip(output.chunks_mut(37)) {
+                a.squeeze(expected);
+                b.squeeze(output);
+            }
+            assert_eq!(&expected[..], &output[..]);
+        }
+    }
+
+    #[cfg(all(feature = "sha3", feature = "lane_complementing"))]
+    #[test]
+    fn test_keccakf_complementing_sponge_matches_keccakf() {
+        use crate::keccakf::KeccakF;
+        use crate::keccakf_complementing::KeccakFComplementing;
+        use crate::KeccakState;
+
+        let input: [u8; 500] = core::array::from_fn(|i| (i * 7) as u8);
+        for rate in &[72, 136, 168] {
+            // the complemented lanes are spread over the whole state, so the rates differ in
+            // which of them are absorbed into and squeezed from
+            let mut a = KeccakState::<KeccakF>::new(*rate, 0x06);
+            let mut b = KeccakState::<KeccakFComplementing>::new(*rate, 0x06);
+            for piece in input.chunks(13) {
+                a.update(piece);
+                b.update(piece);
+            }
+
+            let mut expected = [0u8; 400];
+            let mut output = [0u8; 400];
+            for (expected, output) in expected.chunks_mut(37).zip(output.chunks_mut(37)) {
+                a.squeeze(expected);
+                b.squeeze(output);
+            }
+            assert_eq!(&expected[..], &output[..]);
+
+            // squeezing the empty state exercises the complemented zero state
+            let mut a = KeccakState::<KeccakF>::new(*rate, 0x06);
+            let mut b = KeccakState::<KeccakFComplementing>::new(*rate, 0x06);
+            a.squeeze(&mut expected);
+            b.squeeze(&mut output);
+            assert_eq!(&expected[..], &output[..]);
+        }
+    }
+
+    #[cfg(all(feature = "sha3", feature = "fault_detection"))]
+    #[test]
+    fn test_redundant_detects_fault() {
+        use crate::{keccakf, FaultDetected, Hasher, KeccakState, Permutation, Redundant, Sha3};
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static FAULT_AT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+        /// `keccak-f[1600]` which flips a bit of the state on its `FAULT_AT`th call.
+        struct Glitch;
+
+        impl Permutation for Glitch {
+            type Lane = u64;
+
+            fn execute(state: &mut [u64; 25]) {
+                keccakf(state);
+                if CALLS.fetch_add(1, Ordering::SeqCst) == FAULT_AT.load(Ordering::SeqCst) {
+                    state[7] ^= 1 << 5;
+                }
+            }
+        }
+
+        let input: [u8; 300] = core::array::from_fn(|i| (i * 7) as u8);
+        let mut expected = [0u8; 32];
+        let mut sha3 = Sha3::v256();
+        sha3.update(&input);
+        sha3.finalize(&mut expected);
+
+        let mut output = [0u8; 32];
+        let mut state = KeccakState::<Redundant<Glitch>>::new(136, 0x06);
+        state.update(&input);
+        assert_eq!(state.try_finalize(&mut output), Ok(()));
+        assert_eq!(expected, output);
+
+        // the input is absorbed with 2 permutations and squeezed with a third one, each of them
+        // computed twice, so the faulty state never reaches the output
+        for fault_at in 0..6 {
+            CALLS.store(0, Ordering::SeqCst);
+            FAULT_AT.store(fault_at, Ordering::SeqCst);
+            let mut output = [0xaa; 32];
+            let mut state = KeccakState::<Redundant<Glitch>>::new(136, 0x06);
+            state.update(&input);
+            assert_eq!(state.try_finalize(&mut output), Err(FaultDetected));
+            assert_eq!([0xaa; 32], output);
+        }
+
+        // a fault between two squeezed blocks zeroes the block already written
+        CALLS.store(0, Ordering::SeqCst);
+        FAULT_AT.store(6, Ordering::SeqCst);
+        let mut long_output = [0xaa; 200];
+        let mut state = KeccakState::<Redundant<Glitch>>::new(136, 0x06);
+        state.update(&input);
+        assert_eq!(state.try_finalize(&mut long_output), Err(FaultDetected));
+        assert_eq!(&[0u8; 136][..], &long_output[..136]);
+        assert_eq!(&[0xaa; 64][..], &long_output[136..]);
+
+        // resetting the state clears the fault
+        CALLS.store(0, Ordering::SeqCst);
+        FAULT_AT.store(0, Ordering::SeqCst);
+        let mut state = KeccakState::<Redundant<Glitch>>::new(136, 0x06);
+        state.update(&input);
+        state.reset();
+        state.update(&input);
+        assert_eq!(state.try_finalize(&mut output), Ok(()));
+        assert_eq!(expected, output);
+    }
+
+    #[cfg(all(feature = "sha3", feature = "fault_detection"))]
+    #[test]
+    #[should_panic(expected = "fault detected in the permutation")]
+    fn test_redundant_panics_on_fault() {
+        use crate::{keccakf, KeccakState, Permutation, Redundant};
+        use core::sync::atomic::{AtomicBool, Ordering};
+
+        static FAULTED: AtomicBool = AtomicBool::new(false);
+
+        /// `keccak-f[1600]` which flips a bit of the state on its first call.
+        struct Glitch;
+
+        impl Permutation for Glitch {
+            type Lane = u64;
+
+            fn execute(state: &mut [u64; 25]) {
+                keccakf(state);
+                if !FAULTED.swap(true, Ordering::SeqCst) {
+                    state[0] ^= 1;
+                }
+            }
+        }
+
+        let mut state = KeccakState::<Redundant<Glitch>>::new(136, 0x06);
+        state.update(b"hello world");
+        state.squeeze(&mut [0u8; 32]);
+    }
+
+    #[cfg(all(feature = "sha3", feature = "fault_detection"))]
+    #[test]
+    fn test_redundant_batch_detects_fault() {
+        use crate::times::{sponge_batch, PermutationTimes};
+        use crate::{keccakf, KeccakF, Permutation, Redundant, Sha3};
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::panic;
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static FAULT_AT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+        /// `keccak-f[1600]` which flips a bit of the (last) state on its `FAULT_AT`th call. A
+        /// `small` build hashes the batch with `execute`, every other one with `execute_times`.
+        struct Glitch;
+
+        impl Permutation for Glitch {
+            type Lane = u64;
+
+            fn execute(state: &mut [u64; 25]) {
+                keccakf(state);
+                if CALLS.fetch_add(1, Ordering::SeqCst) == FAULT_AT.load(Ordering::SeqCst) {
+                    state[7] ^= 1 << 5;
+                }
+            }
+        }
+
+        impl PermutationTimes for Glitch {
+            const ROUNDS: usize = KeccakF::ROUNDS;
+
+            fn execute_times<const N: usize>(a: &mut [[u64; N]; 25]) {
+                KeccakF::execute_times(a);
+                if CALLS.fetch_add(1, Ordering::SeqCst) == FAULT_AT.load(Ordering::SeqCst) {
+                    a[7][N - 1] ^= 1 << 5;
+                }
+            }
+        }
+
+        let inputs = [[0x5a; 300]; 8];
+        let mut expected = [[0u8; 32]; 8];
+        Sha3::v256_batch(&inputs, &mut expected);
+
+        let mut outputs = [[0u8; 32]; 8];
+        sponge_batch::<Redundant<Glitch>, _, _>(136, 0x06, &inputs, &mut outputs);
+        assert_eq!(expected, outputs);
+
+        // the inputs are absorbed with 2 permutations and squeezed with a third one, each of
+        // them computed twice
+        for fault_at in 0..6 {
+            CALLS.store(0, Ordering::SeqCst);
+            FAULT_AT.store(fault_at, Ordering::SeqCst);
+            let mut outputs = [[0xaa; 32]; 8];
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                sponge_batch::<Redundant<Glitch>, _, _>(136, 0x06, &inputs, &mut outputs);
+            }));
+            assert!(result.is_err());
+            assert_eq!([[0xaa; 32]; 8], outputs);
+        }
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_resumable_permutations_match_execute() {
+        use crate::keccakf::KeccakF;
+        use crate::keccakf_interleaved::KeccakFInterleaved;
+        use crate::resumable::ResumablePermutation;
+        use crate::Buffer;
+
+        fn check<P: ResumablePermutation>() {
+            let mut expected = Buffer::<P::Lane>::default();
+            expected.xorin(&[0x5a; 200], 0, 200);
+            let mut state = expected.clone();
+            P::execute(expected.words());
+            for start in (0..P::ROUNDS).step_by(5) {
+                let end = core::cmp::min(start + 5, P::ROUNDS);
+                P::execute_rounds(state.words(), start..end);
+            }
+
+            let (mut a, mut b) = ([0u8; 200], [0u8; 200]);
+            expected.setout(&mut a, 0, 200);
+            state.setout(&mut b, 0, 200);
+            assert_eq!(&a[..], &b[..]);
+        }
+
+        check::<KeccakF>();
+        check::<KeccakFInterleaved>();
+        #[cfg(feature = "lane_complementing")]
+        check::<crate::keccakf_complementing::KeccakFComplementing>();
+    }
+
+    #[test]
+    fn test_buffer_lanes_match_bytes() {
+        use crate::{Buffer, Lane, WORDS};
+
+        fn xorin_in_place<L: Lane>(buffer: &mut Buffer<L>, src: &[u8], offset: usize) {
+            buffer.execute(offset, src.len(), |state| {
+                for (state, src) in state.iter_mut().zip(src) {
+                    *state ^= src;
+                }
+            });
+        }
+
+        fn check<L: Lane>() {
+            let size = WORDS * Buffer::<L>::LANE_BYTES;
+            let src: [u8; 200] = core::array::from_fn(|i| (i * 7 + 1) as u8);
+            for offset in 0..20 {
+                for len in 0..core::cmp::min(40, size - offset) {
+                    // unaligned pieces on a state which is not all zero
+                    let mut a = Buffer::<L>::default();
+                    let mut b = Buffer::<L>::default();
+                    a.xorin(&src, 0, size / 2);
+                    xorin_in_place(&mut b, &src[..size / 2], 0);
+                    a.xorin(&src, offset, len);
+                    xorin_in_place(&mut b, &src[..len], offset);
+
+                    let mut expected = [0u8; 200];
+                    let mut output = [0u8; 200];
+                    b.execute(0, size, |state| expected[..size].copy_from_slice(state));
+                    a.setout(&mut output, 0, size);
+                    assert_eq!(&expected[..], &output[..]);
+
+                    let mut expected = [0u8; 40];
+                    let mut output = [0u8; 40];
+                    b.execute(offset, len, |state| expected[..len].copy_from_slice(state));
+                    a.setout(&mut output, offset, len);
+                    assert_eq!(expected, output);
+
+                    a.pad(offset, 0x1f, size - len);
+                    xorin_in_place(&mut b, &[0x1f], offset);
+                    xorin_in_place(&mut b, &[0x80], size - len - 1);
+                    a.setout(&mut output, offset, len);
+                    b.execute(offset, len, |state| expected[..len].copy_from_slice(state));
+                    assert_eq!(expected, output);
+                }
+            }
+        }
+
+        check::<u8>();
+        check::<u16>();
+        check::<u32>();
+        check::<u64>();
+        #[cfg(feature = "sha3")]
+        check::<crate::keccakf_interleaved::Interleaved>();
+        #[cfg(feature = "lane_complementing")]
+        check::<crate::keccakf_complementing::Complemented>();
+    }
 }
@@ -0,0 +1,97 @@
+//! The `MarsupilamiFourteen` hash function defined [`here`].
+//!
+//! [`here`]: https://eprint.iacr.org/2016/770.pdf
+
+use crate::{k12::Tree, keccakp14::KeccakP14, Hasher, IntoXof, KeccakState, Xof};
+
+/// The `MarsupilamiFourteen` hash function defined [`here`].
+///
+/// `MarsupilamiFourteen` is the higher security margin sibling of [`KangarooTwelve`]. It uses
+/// the same tree hashing mode, but with 14 rounds of `Keccak-p[1600]`, a security level of 256
+/// bits and 64-byte chaining values.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["m14"] }
+/// ```
+///
+/// [`here`]: https://eprint.iacr.org/2016/770.pdf
+/// [`KangarooTwelve`]: struct.KangarooTwelve.html
+#[derive(Clone)]
+pub struct MarsupilamiFourteen<T> {
+    tree: Tree<KeccakP14, T>,
+}
+
+impl<T> MarsupilamiFourteen<T> {
+    /// Creates  new [`MarsupilamiFourteen`] hasher with a security level of 256 bits.
+    ///
+    /// [`MarsupilamiFourteen`]: struct.MarsupilamiFourteen.html
+    pub fn new(custom_string: T) -> Self {
+        MarsupilamiFourteen {
+            tree: Tree::new(custom_string, 256),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> Hasher for MarsupilamiFourteen<T> {
+    fn update(&mut self, input: &[u8]) {
+        self.tree.update(input);
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        let mut xof = self.into_xof();
+        xof.squeeze(output);
+    }
+}
+
+/// The `MarsupilamiFourteen` extendable-output function defined [`here`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["m14"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{MarsupilamiFourteen, Xof, IntoXof, Hasher};
+/// let input = b"hello world";
+/// let mut output = [0u8; 128];
+/// let mut hasher = MarsupilamiFourteen::new(b"");
+/// hasher.update(input);
+/// let mut xof = hasher.into_xof();
+/// xof.squeeze(&mut output[..64]);
+/// xof.squeeze(&mut output[64..]);
+/// ```
+///
+/// ---
+///
+/// [`MarsupilamiFourteenXof`] can be created only by using [`MarsupilamiFourteen::IntoXof`] interface.
+///
+/// [`here`]: https://eprint.iacr.org/2016/770.pdf
+/// [`MarsupilamiFourteenXof`]: struct.MarsupilamiFourteenXof.html
+/// [`MarsupilamiFourteen::IntoXof`]: struct.MarsupilamiFourteen.html#impl-IntoXof
+#[derive(Clone)]
+pub struct MarsupilamiFourteenXof {
+    state: KeccakState<KeccakP14>,
+}
+
+impl<T: AsRef<[u8]>> IntoXof for MarsupilamiFourteen<T> {
+    type Xof = MarsupilamiFourteenXof;
+
+    fn into_xof(self) -> MarsupilamiFourteenXof {
+        MarsupilamiFourteenXof {
+            state: self.tree.into_state(),
+        }
+    }
+}
+
+impl Xof for MarsupilamiFourteenXof {
+    fn squeeze(&mut self, output: &mut [u8]) {
+        self.state.squeeze(output);
+    }
+}
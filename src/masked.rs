@@ -0,0 +1,467 @@
+//! Boolean-masked `keccak-f[1600, 24]`, and the `SHAKE` and `KMAC` functions built on it.
+//!
+//! Every secret value is split into `D` shares whose xor is the value, so that no intermediate
+//! result of the computation depends on fewer than `D` shares. The linear steps of the
+//! permutation are applied to every share on its own, while `Chi` multiplies the shares with the
+//! gadget of Ishai, Sahai and Wagner, consuming fresh randomness from a caller-supplied [`Rng`].
+//! `D` shares protect against attacks of order `D - 1`.
+//!
+//! Masking in software only holds as long as the compiler and the CPU do not recombine the
+//! shares, so the resulting binary must still be evaluated for leakage on its target.
+//!
+//! [`Rng`]: trait.Rng.html
+
+use crate::{bits_to_rate, left_encode, right_encode, Buffer, Mode, PI, RC, RHO, WORDS};
+
+/// A source of the fresh randomness consumed by the masked permutation.
+///
+/// It should be backed by a cryptographically secure generator, e.g. a hardware TRNG.
+pub trait Rng {
+    /// Returns 64 fresh uniformly random bits.
+    fn next_u64(&mut self) -> u64;
+}
+
+/// Splits `value` into the shares `shares`, whose xor is `value`.
+///
+/// # Panics
+///
+/// Panics if the shares are not as long as `value`.
+pub fn mask<R: Rng, const D: usize>(value: &[u8], shares: [&mut [u8]; D], rng: &mut R) {
+    let mut shares = shares;
+    for share in shares.iter() {
+        assert_eq!(
+            share.len(),
+            value.len(),
+            "shares must be as long as the value"
+        );
+    }
+
+    let (first, rest) = shares.split_at_mut(1);
+    first[0].copy_from_slice(value);
+    for share in rest.iter_mut() {
+        for chunk in share.chunks_mut(8) {
+            let random = rng.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&random[..chunk.len()]);
+        }
+        for (byte, random) in first[0].iter_mut().zip(share.iter()) {
+            *byte ^= random;
+        }
+    }
+}
+
+/// Recombines the shares `shares` into `value`.
+///
+/// # Panics
+///
+/// Panics if the shares are not as long as `value`.
+pub fn unmask<const D: usize>(shares: [&[u8]; D], value: &mut [u8]) {
+    value.iter_mut().for_each(|byte| *byte = 0);
+    for share in shares.iter() {
+        assert_eq!(
+            share.len(),
+            value.len(),
+            "shares must be as long as the value"
+        );
+        for (byte, share) in value.iter_mut().zip(share.iter()) {
+            *byte ^= share;
+        }
+    }
+}
+
+/// Computes the shares of `x & y` from the shares of `x` and `y`.
+#[inline(always)]
+fn and<R: Rng, const D: usize>(x: &[u64; D], y: &[u64; D], rng: &mut R) -> [u64; D] {
+    let mut z = [0u64; D];
+    for i in 0..D {
+        z[i] = x[i] & y[i];
+    }
+    for i in 0..D {
+        for j in i + 1..D {
+            let r = rng.next_u64();
+            z[i] ^= r;
+            // the brackets matter: every partial result must stay masked by `r`
+            z[j] ^= (r ^ (x[i] & y[j])) ^ (x[j] & y[i]);
+        }
+    }
+    z
+}
+
+/// `keccak-f[1600, 24]` applied to a state split into `D` shares.
+///
+/// The xor of the resulting shares is the permutation of the xor of the given ones.
+///
+/// # Panics
+///
+/// Panics if `D` is lower than 2.
+pub fn keccakf_masked<R: Rng, const D: usize>(shares: &mut [[u64; WORDS]; D], rng: &mut R) {
+    assert!(D >= 2, "masking requires at least 2 shares");
+
+    for rc in RC.iter() {
+        for a in shares.iter_mut() {
+            // Theta
+            let mut array = [0u64; 5];
+            for (x, column) in array.iter_mut().enumerate() {
+                *column = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            }
+            for x in 0..5 {
+                let d = array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+                for y in 0..5 {
+                    a[5 * y + x] ^= d;
+                }
+            }
+
+            // Rho and pi
+            let mut last = a[1];
+            for (pi, rho) in PI.iter().zip(RHO.iter()) {
+                let current = a[*pi];
+                a[*pi] = last.rotate_left(*rho);
+                last = current;
+            }
+        }
+
+        // Chi
+        for y in 0..5 {
+            let mut row = [[0u64; D]; 5];
+            for (x, lane) in row.iter_mut().enumerate() {
+                for (share, a) in lane.iter_mut().zip(shares.iter()) {
+                    *share = a[5 * y + x];
+                }
+            }
+            for x in 0..5 {
+                // complementing the first share complements the value
+                let mut not = row[(x + 1) % 5];
+                not[0] = !not[0];
+                let product = and(&not, &row[(x + 2) % 5], rng);
+                for (a, product) in shares.iter_mut().zip(product.iter()) {
+                    a[5 * y + x] ^= product;
+                }
+            }
+        }
+
+        // Iota
+        shares[0][0] ^= rc;
+    }
+}
+
+/// A sponge whose state is split into `D` shares.
+struct MaskedState<R, const D: usize> {
+    shares: [Buffer; D],
+    offset: usize,
+    rate: usize,
+    delim: u8,
+    mode: Mode,
+    rng: R,
+}
+
+impl<R: Rng, const D: usize> MaskedState<R, D> {
+    fn new(rate: usize, delim: u8, rng: R) -> Self {
+        MaskedState {
+            shares: core::array::from_fn(|_| Buffer::default()),
+            offset: 0,
+            rate,
+            delim,
+            mode: Mode::Absorbing,
+            rng,
+        }
+    }
+
+    fn keccak(&mut self) {
+        let mut state = [[0u64; WORDS]; D];
+        for (state, share) in state.iter_mut().zip(self.shares.iter()) {
+            *state = share.0;
+        }
+        keccakf_masked(&mut state, &mut self.rng);
+        for (state, share) in state.iter().zip(self.shares.iter_mut()) {
+            share.0 = *state;
+        }
+    }
+
+    /// Absorbs the shares of a secret input.
+    fn update(&mut self, input: [&[u8]; D]) {
+        let len = input[0].len();
+        for share in input.iter() {
+            assert_eq!(share.len(), len, "all shares must be equally long");
+        }
+        if let Mode::Squeezing = self.mode {
+            self.mode = Mode::Absorbing;
+            self.fill_block();
+        }
+
+        let mut ip = 0;
+        let mut l = len;
+        let mut rate = self.rate - self.offset;
+        let mut offset = self.offset;
+        while l >= rate {
+            for (share, input) in self.shares.iter_mut().zip(input.iter()) {
+                share.xorin(&input[ip..], offset, rate);
+            }
+            self.keccak();
+            ip += rate;
+            l -= rate;
+            rate = self.rate;
+            offset = 0;
+        }
+
+        for (share, input) in self.shares.iter_mut().zip(input.iter()) {
+            share.xorin(&input[ip..], offset, l);
+        }
+        self.offset = offset + l;
+    }
+
+    /// Absorbs a public input, which needs no masking.
+    fn update_public(&mut self, input: &[u8]) {
+        let mut shares: [&[u8]; D] = [&[]; D];
+        let zeros = [0u8; 200];
+        for chunk in input.chunks(zeros.len()) {
+            shares[0] = chunk;
+            for share in shares[1..].iter_mut() {
+                *share = &zeros[..chunk.len()];
+            }
+            self.update(shares);
+        }
+    }
+
+    fn fill_block(&mut self) {
+        self.keccak();
+        self.offset = 0;
+    }
+
+    fn squeeze(&mut self, output: [&mut [u8]; D]) {
+        let mut output = output;
+        let len = output[0].len();
+        for share in output.iter() {
+            assert_eq!(share.len(), len, "all shares must be equally long");
+        }
+        if let Mode::Absorbing = self.mode {
+            self.mode = Mode::Squeezing;
+            self.shares[0].pad(self.offset, self.delim, self.rate);
+            self.fill_block();
+        }
+
+        let mut op = 0;
+        let mut l = len;
+        let mut rate = self.rate - self.offset;
+        let mut offset = self.offset;
+        while l >= rate {
+            for (share, output) in self.shares.iter_mut().zip(output.iter_mut()) {
+                share.setout(&mut output[op..], offset, rate);
+            }
+            self.keccak();
+            op += rate;
+            l -= rate;
+            rate = self.rate;
+            offset = 0;
+        }
+
+        for (share, output) in self.shares.iter_mut().zip(output.iter_mut()) {
+            share.setout(&mut output[op..], offset, l);
+        }
+        self.offset = offset + l;
+    }
+}
+
+/// The `SHAKE` extendable-output functions defined in [`FIPS-202`], computed on a state split
+/// into `D` shares.
+///
+/// The input is given, and the output is returned, as `D` equally long shares whose xor is the
+/// actual value.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["masked"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{mask, unmask, MaskedShake, Rng};
+/// # struct Trng(u64);
+/// # impl Rng for Trng {
+/// #     fn next_u64(&mut self) -> u64 {
+/// #         self.0 ^= self.0 << 13;
+/// #         self.0 ^= self.0 >> 7;
+/// #         self.0 ^= self.0 << 17;
+/// #         self.0
+/// #     }
+/// # }
+/// # let mut rng = Trng(0x2545f4914f6cdd1d);
+/// let (mut secret_a, mut secret_b) = ([0u8; 32], [0u8; 32]);
+/// mask(&[0x42; 32], [&mut secret_a, &mut secret_b], &mut rng);
+///
+/// let mut shake = MaskedShake::<_, 2>::v256(rng);
+/// shake.update([&secret_a, &secret_b]);
+/// let (mut output_a, mut output_b) = ([0u8; 64], [0u8; 64]);
+/// shake.finalize([&mut output_a, &mut output_b]);
+///
+/// let mut output = [0u8; 64];
+/// unmask([&output_a, &output_b], &mut output);
+/// ```
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+pub struct MaskedShake<R, const D: usize> {
+    state: MaskedState<R, D>,
+}
+
+impl<R: Rng, const D: usize> MaskedShake<R, D> {
+    const DELIM: u8 = 0x1f;
+
+    /// Creates  new [`MaskedShake`] hasher with a security level of 128 bits.
+    ///
+    /// [`MaskedShake`]: struct.MaskedShake.html
+    pub fn v128(rng: R) -> Self {
+        MaskedShake::new(128, rng)
+    }
+
+    /// Creates  new [`MaskedShake`] hasher with a security level of 256 bits.
+    ///
+    /// [`MaskedShake`]: struct.MaskedShake.html
+    pub fn v256(rng: R) -> Self {
+        MaskedShake::new(256, rng)
+    }
+
+    fn new(bits: usize, rng: R) -> Self {
+        MaskedShake {
+            state: MaskedState::new(bits_to_rate(bits), Self::DELIM, rng),
+        }
+    }
+
+    /// Absorbs the shares of additional input. Can be called multiple times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares are not equally long.
+    pub fn update(&mut self, input: [&[u8]; D]) {
+        self.state.update(input);
+    }
+
+    /// Squeezes the shares of another part of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares are not equally long.
+    pub fn squeeze(&mut self, output: [&mut [u8]; D]) {
+        self.state.squeeze(output);
+    }
+
+    /// Pads and squeezes the shares of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares are not equally long.
+    pub fn finalize(mut self, output: [&mut [u8]; D]) {
+        self.state.squeeze(output);
+    }
+}
+
+/// The `KMAC` pseudo-random functions defined in [`SP800-185`], computed on a state split into
+/// `D` shares.
+///
+/// The key and the input are given, and the output is returned, as `D` equally long shares whose
+/// xor is the actual value. The customization string is public.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["masked"] }
+/// ```
+///
+/// [`SP800-185`]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-185.pdf
+pub struct MaskedKmac<R, const D: usize> {
+    state: MaskedState<R, D>,
+}
+
+impl<R: Rng, const D: usize> MaskedKmac<R, D> {
+    const DELIM: u8 = 0x04;
+
+    /// Creates  new [`MaskedKmac`] hasher with a security level of 128 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares of the key are not equally long.
+    ///
+    /// [`MaskedKmac`]: struct.MaskedKmac.html
+    pub fn v128(key: [&[u8]; D], custom_string: &[u8], rng: R) -> Self {
+        MaskedKmac::new(key, custom_string, 128, rng)
+    }
+
+    /// Creates  new [`MaskedKmac`] hasher with a security level of 256 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares of the key are not equally long.
+    ///
+    /// [`MaskedKmac`]: struct.MaskedKmac.html
+    pub fn v256(key: [&[u8]; D], custom_string: &[u8], rng: R) -> Self {
+        MaskedKmac::new(key, custom_string, 256, rng)
+    }
+
+    fn new(key: [&[u8]; D], custom_string: &[u8], bits: usize, rng: R) -> Self {
+        let rate = bits_to_rate(bits);
+        let mut state = MaskedState::new(rate, Self::DELIM, rng);
+        // cSHAKE with the name `KMAC`, as in `Kmac`
+        state.update_public(left_encode(rate).value());
+        state.update_public(left_encode(32).value());
+        state.update_public(b"KMAC");
+        state.update_public(left_encode(custom_string.len() * 8).value());
+        state.update_public(custom_string);
+        state.fill_block();
+
+        state.update_public(left_encode(rate).value());
+        state.update_public(left_encode(key[0].len() * 8).value());
+        state.update(key);
+        state.fill_block();
+        MaskedKmac { state }
+    }
+
+    /// Absorbs the shares of additional input. Can be called multiple times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares are not equally long.
+    pub fn update(&mut self, input: [&[u8]; D]) {
+        self.state.update(input);
+    }
+
+    /// Pads and squeezes the shares of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares are not equally long.
+    pub fn finalize(mut self, output: [&mut [u8]; D]) {
+        self.state
+            .update_public(right_encode(output[0].len() * 8).value());
+        self.state.squeeze(output);
+    }
+
+    /// Converts the hasher into the masked `KMACXOF` extendable-output function.
+    pub fn into_xof(mut self) -> MaskedKmacXof<R, D> {
+        self.state.update_public(right_encode(0).value());
+        MaskedKmacXof { state: self.state }
+    }
+}
+
+/// The `KMACXOF` extendable-output functions defined in [`SP800-185`], computed on a state split
+/// into `D` shares.
+///
+/// [`MaskedKmacXof`] can be created only by using [`MaskedKmac::into_xof`].
+///
+/// [`SP800-185`]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-185.pdf
+/// [`MaskedKmacXof`]: struct.MaskedKmacXof.html
+/// [`MaskedKmac::into_xof`]: struct.MaskedKmac.html#method.into_xof
+pub struct MaskedKmacXof<R, const D: usize> {
+    state: MaskedState<R, D>,
+}
+
+impl<R: Rng, const D: usize> MaskedKmacXof<R, D> {
+    /// Squeezes the shares of another part of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shares are not equally long.
+    pub fn squeeze(&mut self, output: [&mut [u8]; D]) {
+        self.state.squeeze(output);
+    }
+}
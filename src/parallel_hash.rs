@@ -1,4 +1,9 @@
-use crate::{left_encode, right_encode, CShake, Hasher, IntoXof, Xof};
+#[cfg(feature = "parallel")]
+use crate::times::sponge_batch_parallel;
+use crate::{
+    bits_to_rate, keccakf::KeccakF, left_encode, right_encode, times::sponge_batch, CShake, Hasher,
+    IntoXof, Xof,
+};
 
 #[derive(Clone)]
 struct UnfinishedState {
@@ -84,6 +89,63 @@ impl ParallelHash {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl ParallelHash {
+    /// The number of blocks hashed in parallel before their outputs are absorbed, which bounds
+    /// the memory used for them.
+    const PARALLEL_BLOCKS: usize = 8192;
+
+    /// Absorbs additional input like [`update`], spreading the hashing of its whole blocks
+    /// across the `rayon` thread pool. The output is identical to the one of the sequential
+    /// path, and both can be mixed freely.
+    ///
+    /// # Usage
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// tiny-keccak = { version = "2.0.0", features = ["parallel_hash", "parallel"] }
+    /// ```
+    ///
+    /// [`update`]: trait.Hasher.html#tymethod.update
+    pub fn update_parallel(&mut self, input: &[u8]) {
+        // complete the unfinished block first, so the remaining input starts at a block boundary
+        let todo = match self.unfinished {
+            Some(ref unfinished) => {
+                core::cmp::min(self.block_size - unfinished.absorbed, input.len())
+            }
+            None => 0,
+        };
+        self.update(&input[..todo]);
+        let input = &input[todo..];
+
+        let blocks = input.len() / self.block_size;
+        if blocks == 0 {
+            self.update(input);
+            return;
+        }
+
+        let (input, rest) = input.split_at(blocks * self.block_size);
+        let window = core::cmp::min(blocks, Self::PARALLEL_BLOCKS);
+        let mut suboutputs = std::vec![[0u8; 64]; window];
+        for input in input.chunks(window * self.block_size) {
+            let suboutputs = &mut suboutputs[..input.len() / self.block_size];
+            // each block is hashed with SHAKE, i.e. cSHAKE without name and customization string
+            sponge_batch_parallel::<KeccakF>(
+                bits_to_rate(self.bits),
+                0x1f,
+                input,
+                self.block_size,
+                suboutputs,
+            );
+            for suboutput in suboutputs.iter() {
+                self.state.update(&suboutput[..self.bits / 4]);
+            }
+            self.blocks += suboutputs.len();
+        }
+        self.update(rest);
+    }
+}
+
 impl Hasher for ParallelHash {
     fn update(&mut self, mut input: &[u8]) {
         if let Some(mut unfinished) = self.unfinished.take() {
@@ -108,17 +170,26 @@ impl Hasher for ParallelHash {
         let input_blocks_end = input.len() / self.block_size * self.block_size;
         let input_blocks = &input[..input_blocks_end];
         let input_end = &input[input_blocks_end..];
-        let parts = input_blocks.chunks(self.block_size).map(|chunk| {
-            let mut state = CShake::new(b"", b"", bits);
-            state.update(chunk);
-            let mut suboutput = Suboutout::security(bits);
-            state.finalize(suboutput.as_bytes_mut());
-            suboutput
-        });
-
-        for part in parts {
-            self.state.update(part.as_bytes());
-            self.blocks += 1;
+
+        // hash up to 8 blocks at once, each with SHAKE, i.e. cSHAKE without name and
+        // customization string
+        for group in input_blocks.chunks(8 * self.block_size) {
+            let mut blocks: [&[u8]; 8] = Default::default();
+            for (dst, block) in blocks.iter_mut().zip(group.chunks(self.block_size)) {
+                *dst = block;
+            }
+            let count = group.len() / self.block_size;
+            let mut suboutputs = [[0u8; 64]; 8];
+            sponge_batch::<KeccakF, _, _>(
+                bits_to_rate(bits),
+                0x1f,
+                &blocks[..count],
+                &mut suboutputs[..count],
+            );
+            for suboutput in &suboutputs[..count] {
+                self.state.update(&suboutput[..bits / 4]);
+            }
+            self.blocks += count;
         }
 
         if !input_end.is_empty() {
@@ -0,0 +1,87 @@
+//! A permutation computed twice, to detect injected faults.
+
+use crate::{resumable::ResumablePermutation, FaultDetected, Permutation, WORDS};
+use core::ops::Range;
+
+/// A [`Permutation`] which computes `P` twice and compares both results.
+///
+/// A fault injected into either computation, e.g. by a voltage or clock glitch, makes the
+/// results differ. The sponges record the fault and never release output afterwards: the
+/// fallible `try_finalize` of [`Sha3`] and [`Kmac`] returns [`FaultDetected`], while the
+/// infallible `finalize` and `squeeze`, also those of a [`Sponge`], panic. So do the stepped
+/// methods, like [`Sha3::update_step`], which compute the permutation a few rounds at a time,
+/// and the batch functions, like [`Sha3::v256_batch`].
+///
+/// The second computation runs on a copy of the state hidden from the optimizer, so that it is
+/// not merged with the first one. A fault which hits both computations the same way stays
+/// undetected.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["fault_detection"] }
+/// ```
+///
+/// [`Permutation`]: trait.Permutation.html
+/// [`Sha3`]: struct.Sha3.html
+/// [`Kmac`]: struct.Kmac.html
+/// [`FaultDetected`]: struct.FaultDetected.html
+/// [`Sponge`]: struct.Sponge.html
+/// [`Sha3::update_step`]: struct.Sha3.html#method.update_step
+/// [`Sha3::v256_batch`]: struct.Sha3.html#method.v256_batch
+#[derive(Clone, Copy, Debug)]
+pub struct Redundant<P>(core::marker::PhantomData<P>);
+
+impl<P: Permutation> Permutation for Redundant<P> {
+    type Lane = P::Lane;
+
+    fn execute(state: &mut [Self::Lane; WORDS]) {
+        if let Err(err) = Self::execute_checked(state) {
+            panic!("{}", err);
+        }
+    }
+
+    fn execute_checked(state: &mut [Self::Lane; WORDS]) -> Result<(), FaultDetected> {
+        let mut copy = *state;
+        P::execute(state);
+        P::execute(core::hint::black_box(&mut copy));
+        if *state != copy {
+            return Err(FaultDetected);
+        }
+        Ok(())
+    }
+}
+
+impl<P: ResumablePermutation> ResumablePermutation for Redundant<P> {
+    const ROUNDS: usize = P::ROUNDS;
+
+    fn execute_rounds(state: &mut [Self::Lane; WORDS], rounds: Range<usize>) {
+        let mut copy = *state;
+        P::execute_rounds(state, rounds.clone());
+        P::execute_rounds(core::hint::black_box(&mut copy), rounds);
+        if *state != copy {
+            panic!("{}", FaultDetected);
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "keccak",
+    feature = "sha3",
+    feature = "shake",
+    feature = "k12",
+    feature = "parallel_hash"
+))]
+impl<P: crate::times::PermutationTimes> crate::times::PermutationTimes for Redundant<P> {
+    const ROUNDS: usize = P::ROUNDS;
+
+    fn execute_times<const N: usize>(a: &mut [[u64; N]; WORDS]) {
+        let mut copy = *a;
+        P::execute_times(a);
+        P::execute_times(core::hint::black_box(&mut copy));
+        if *a != copy {
+            panic!("{}", FaultDetected);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Permutations computed a limited number of rounds at a time.
+//!
+//! On slow microcontrollers a whole permutation may not fit into the time budget of a task.
+//! [`ResumableKeccak`] and the stepped methods of the sponges, like [`Sha3::update_step`], apply
+//! at most a given number of rounds per call and return [`Poll::Pending`] until they are done,
+//! so that long computations can be interleaved with other work without threads.
+//!
+//! [`ResumableKeccak`]: ../struct.ResumableKeccak.html
+//! [`Sha3::update_step`]: ../struct.Sha3.html#method.update_step
+//! [`Poll::Pending`]: https://doc.rust-lang.org/core/task/enum.Poll.html
+
+use crate::{keccak_rounds, KeccakState, Mode, Permutation, MAX_ROUNDS, RC, WORDS};
+use core::ops::Range;
+use core::task::Poll;
+
+/// A permutation which can be applied a few rounds at a time.
+#[cfg_attr(
+    not(any(feature = "keccak", feature = "sha3", feature = "shake")),
+    allow(dead_code)
+)]
+pub(crate) trait ResumablePermutation: Permutation {
+    /// The number of rounds of the permutation.
+    const ROUNDS: usize;
+
+    /// Applies the rounds `rounds`, out of `0..ROUNDS`, to the state.
+    fn execute_rounds(state: &mut [Self::Lane; WORDS], rounds: Range<usize>);
+}
+
+/// `keccak-p[1600, n_r]` computed at most a given number of rounds per call.
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{keccak_p, ResumableKeccak};
+/// # use core::task::Poll;
+/// let mut state = [0u64; 25];
+/// let mut permutation = ResumableKeccak::keccakf();
+/// while permutation.resume(&mut state, 5) == Poll::Pending {
+///     // other work
+/// }
+///
+/// let mut expected = [0u64; 25];
+/// keccak_p(&mut expected, 24);
+/// assert_eq!(expected, state);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ResumableKeccak {
+    rounds: usize,
+    done: usize,
+}
+
+impl ResumableKeccak {
+    /// Creates  new [`ResumableKeccak`] computing `keccak-f[1600, 24]`.
+    ///
+    /// [`ResumableKeccak`]: struct.ResumableKeccak.html
+    pub fn keccakf() -> ResumableKeccak {
+        ResumableKeccak::keccak_p(MAX_ROUNDS)
+    }
+
+    /// Creates  new [`ResumableKeccak`] computing `keccak-p[1600, rounds]`, like [`keccak_p`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rounds` is not in range `1..=24`.
+    ///
+    /// [`ResumableKeccak`]: struct.ResumableKeccak.html
+    /// [`keccak_p`]: fn.keccak_p.html
+    pub fn keccak_p(rounds: usize) -> ResumableKeccak {
+        assert!(
+            (1..=MAX_ROUNDS).contains(&rounds),
+            "number of rounds must be in range 1..=24"
+        );
+        ResumableKeccak { rounds, done: 0 }
+    }
+
+    /// Returns the number of rounds left until the permutation completes.
+    pub fn remaining(&self) -> usize {
+        self.rounds - self.done
+    }
+
+    /// Applies at most `budget` further rounds to the state.
+    ///
+    /// Returns [`Poll::Ready`] once all rounds are applied, after which the next call starts a
+    /// new permutation. The state must not be modified while the permutation is pending.
+    ///
+    /// [`Poll::Ready`]: https://doc.rust-lang.org/core/task/enum.Poll.html
+    pub fn resume(&mut self, state: &mut [u64; WORDS], budget: usize) -> Poll<()> {
+        let rounds = core::cmp::min(budget, self.remaining());
+        let start = MAX_ROUNDS - self.rounds + self.done;
+        keccak_rounds(state, &RC[start..start + rounds]);
+        self.done += rounds;
+        if self.done < self.rounds {
+            return Poll::Pending;
+        }
+        self.done = 0;
+        Poll::Ready(())
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "keccak", feature = "sha3", feature = "shake")),
+    allow(dead_code)
+)]
+impl<P: ResumablePermutation> KeccakState<P> {
+    /// Continues the permutation in progress, if any, with at most `budget` rounds. Returns
+    /// whether it completed.
+    fn resume(&mut self, budget: &mut usize) -> bool {
+        if let Some(round) = self.round {
+            let rounds = core::cmp::min(*budget, P::ROUNDS - round);
+            P::execute_rounds(self.buffer.words(), round..round + rounds);
+            *budget -= rounds;
+            if round + rounds < P::ROUNDS {
+                self.round = Some(round + rounds);
+                return false;
+            }
+            self.round = None;
+        }
+        true
+    }
+
+    /// Starts the permutation of the current block, like `fill_block` does at once.
+    fn start_block(&mut self) {
+        self.round = Some(0);
+        self.offset = 0;
+    }
+
+    /// Absorbs `input` like `update`, applying at most `budget` rounds per call. Must be called
+    /// with the same input until it returns `Poll::Ready`.
+    pub(crate) fn update_step(&mut self, input: &[u8], mut budget: usize) -> Poll<()> {
+        loop {
+            if !self.resume(&mut budget) {
+                return Poll::Pending;
+            }
+            if let Mode::Squeezing = self.mode {
+                self.mode = Mode::Absorbing;
+                self.start_block();
+                continue;
+            }
+
+            let input = &input[self.progress..];
+            let rate = self.rate - self.offset;
+            if input.len() < rate {
+                self.buffer.xorin(input, self.offset, input.len());
+                self.offset += input.len();
+                self.progress = 0;
+                return Poll::Ready(());
+            }
+            self.buffer.xorin(input, self.offset, rate);
+            self.progress += rate;
+            self.start_block();
+        }
+    }
+
+    /// Squeezes `output` like `squeeze`, applying at most `budget` rounds per call. Must be
+    /// called with the same output until it returns `Poll::Ready`.
+    pub(crate) fn squeeze_step(&mut self, output: &mut [u8], mut budget: usize) -> Poll<()> {
+        loop {
+            if !self.resume(&mut budget) {
+                return Poll::Pending;
+            }
+            if let Mode::Absorbing = self.mode {
+                self.mode = Mode::Squeezing;
+                self.pad();
+                self.start_block();
+                continue;
+            }
+
+            let output = &mut output[self.progress..];
+            let rate = self.rate - self.offset;
+            if output.len() < rate {
+                let len = output.len();
+                self.buffer.setout(output, self.offset, len);
+                self.offset += len;
+                self.progress = 0;
+                return Poll::Ready(());
+            }
+            self.buffer.setout(output, self.offset, rate);
+            self.progress += rate;
+            self.start_block();
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
+#[cfg(feature = "fault_detection")]
+use crate::FaultDetected;
+use crate::{
+    bits_to_rate, times::sponge_batch, Hasher, KeccakFChecked, KeccakFTimesChecked, KeccakState,
+};
+use core::task::Poll;
 
 /// The `SHA3` hash functions defined in [`FIPS-202`].
 ///
@@ -31,7 +36,7 @@ use crate::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
 /// ```
 #[derive(Clone)]
 pub struct Sha3 {
-    state: KeccakState<KeccakF>,
+    state: KeccakState<KeccakFChecked>,
 }
 
 impl Sha3 {
@@ -65,11 +70,158 @@ impl Sha3 {
         Sha3::new(512)
     }
 
+    /// Hashes every input with [`Sha3`]-224 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ and, with the `fault_detection`
+    /// feature, if the redundant computation of any permutation did not match. No output is
+    /// written from a faulty state.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v224_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 28]]) {
+        sponge_batch::<KeccakFTimesChecked, _, _>(bits_to_rate(224), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Sha3`]-256 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ and, with the `fault_detection`
+    /// feature, if the redundant computation of any permutation did not match. No output is
+    /// written from a faulty state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Sha3};
+    /// let inputs: [&[u8]; 2] = [b"hello", b"world"];
+    /// let mut outputs = [[0u8; 32]; 2];
+    /// Sha3::v256_batch(&inputs, &mut outputs);
+    ///
+    /// let mut expected = [0u8; 32];
+    /// let mut sha3 = Sha3::v256();
+    /// sha3.update(b"world");
+    /// sha3.finalize(&mut expected);
+    /// assert_eq!(expected, outputs[1]);
+    /// ```
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v256_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 32]]) {
+        sponge_batch::<KeccakFTimesChecked, _, _>(bits_to_rate(256), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Sha3`]-384 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ and, with the `fault_detection`
+    /// feature, if the redundant computation of any permutation did not match. No output is
+    /// written from a faulty state.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v384_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 48]]) {
+        sponge_batch::<KeccakFTimesChecked, _, _>(bits_to_rate(384), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Sha3`]-512 and writes each digest to the corresponding
+    /// output. Up to 8 inputs are hashed in lock-step with a multi-state permutation, which is
+    /// fastest for batches of equally long messages; the results are identical to hashing
+    /// the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ and, with the `fault_detection`
+    /// feature, if the redundant computation of any permutation did not match. No output is
+    /// written from a faulty state.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v512_batch<I: AsRef<[u8]>>(inputs: &[I], outputs: &mut [[u8; 64]]) {
+        sponge_batch::<KeccakFTimesChecked, _, _>(bits_to_rate(512), Self::DELIM, inputs, outputs);
+    }
+
     fn new(bits: usize) -> Sha3 {
         Sha3 {
             state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
         }
     }
+
+    /// Pads and squeezes the digest like [`finalize`], unless the redundant computation of any
+    /// permutation did not match. No byte of a faulty state is ever written to `output`; the
+    /// bytes squeezed before the fault was detected are zeroed.
+    ///
+    /// # Usage
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// tiny-keccak = { version = "2.0.0", features = ["sha3", "fault_detection"] }
+    /// ```
+    ///
+    /// [`finalize`]: trait.Hasher.html#tymethod.finalize
+    #[cfg(feature = "fault_detection")]
+    pub fn try_finalize(self, output: &mut [u8]) -> Result<(), FaultDetected> {
+        self.state.try_finalize(output)
+    }
+
+    /// Absorbs `input` like [`update`], but applies at most `rounds` rounds of the permutation
+    /// per call, so that hashing long inputs can be interleaved with other work.
+    ///
+    /// Returns [`Poll::Pending`] until all of `input` is absorbed. Until then, it must be called
+    /// again with the same `input`, and no other method of the hasher may be called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Sha3};
+    /// # use core::task::Poll;
+    /// let input = [0x42u8; 1000];
+    /// let mut output = [0u8; 32];
+    /// let mut sha3 = Sha3::v256();
+    /// while sha3.update_step(&input, 4) == Poll::Pending {
+    ///     // other work
+    /// }
+    /// let mut pending = sha3.finalize_step(&mut output, 4);
+    /// while let Some(sha3) = pending {
+    ///     // other work
+    ///     pending = sha3.finalize_step(&mut output, 4);
+    /// }
+    ///
+    /// let mut expected = [0u8; 32];
+    /// let mut sha3 = Sha3::v256();
+    /// sha3.update(&input);
+    /// sha3.finalize(&mut expected);
+    /// assert_eq!(expected, output);
+    /// ```
+    ///
+    /// [`update`]: trait.Hasher.html#tymethod.update
+    /// [`Poll::Pending`]: https://doc.rust-lang.org/core/task/enum.Poll.html
+    pub fn update_step(&mut self, input: &[u8], rounds: usize) -> Poll<()> {
+        self.state.update_step(input, rounds)
+    }
+
+    /// Pads and squeezes the digest like [`finalize`], but applies at most `rounds` rounds of
+    /// the permutation per call.
+    ///
+    /// Hands the hasher back until the whole digest is written. Until then, it must be called
+    /// again on the returned hasher with the same `output`. Once it returns `None`, the hasher
+    /// is consumed, like by [`finalize`].
+    ///
+    /// [`finalize`]: trait.Hasher.html#tymethod.finalize
+    pub fn finalize_step(mut self, output: &mut [u8], rounds: usize) -> Option<Sha3> {
+        match self.state.squeeze_step(output, rounds) {
+            Poll::Pending => Some(self),
+            Poll::Ready(()) => None,
+        }
+    }
 }
 
 impl Hasher for Sha3 {
@@ -1,4 +1,7 @@
-use crate::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState, Xof};
+use crate::{
+    bits_to_rate, keccakf::KeccakF, times::sponge_batch, Hasher, KeccakFNative, KeccakState, Xof,
+};
+use core::task::Poll;
 
 /// The `SHAKE` extendable-output functions defined in [`FIPS-202`].
 ///
@@ -12,7 +15,7 @@ use crate::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState, Xof};
 /// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
 #[derive(Clone)]
 pub struct Shake {
-    state: KeccakState<KeccakF>,
+    state: KeccakState<KeccakFNative>,
 }
 
 impl Shake {
@@ -32,11 +35,61 @@ impl Shake {
         Shake::new(256)
     }
 
+    /// Hashes every input with [`Shake`]-128 and fills each corresponding output with
+    /// `L` bytes of its output. Up to 8 inputs are hashed in lock-step with a multi-state
+    /// permutation; the results are identical to hashing the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    pub fn v128_batch<I: AsRef<[u8]>, const L: usize>(inputs: &[I], outputs: &mut [[u8; L]]) {
+        sponge_batch::<KeccakF, _, _>(bits_to_rate(128), Self::DELIM, inputs, outputs);
+    }
+
+    /// Hashes every input with [`Shake`]-256 and fills each corresponding output with
+    /// `L` bytes of its output. Up to 8 inputs are hashed in lock-step with a multi-state
+    /// permutation; the results are identical to hashing the inputs one by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and outputs differ.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    pub fn v256_batch<I: AsRef<[u8]>, const L: usize>(inputs: &[I], outputs: &mut [[u8; L]]) {
+        sponge_batch::<KeccakF, _, _>(bits_to_rate(256), Self::DELIM, inputs, outputs);
+    }
+
     pub(crate) fn new(bits: usize) -> Shake {
         Shake {
             state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
         }
     }
+
+    /// Absorbs `input` like [`update`], but applies at most `rounds` rounds of the permutation
+    /// per call, so that hashing long inputs can be interleaved with other work.
+    ///
+    /// Returns [`Poll::Pending`] until all of `input` is absorbed. Until then, it must be called
+    /// again with the same `input`, and no other method of the hasher may be called.
+    ///
+    /// [`update`]: trait.Hasher.html#tymethod.update
+    /// [`Poll::Pending`]: https://doc.rust-lang.org/core/task/enum.Poll.html
+    pub fn update_step(&mut self, input: &[u8], rounds: usize) -> Poll<()> {
+        self.state.update_step(input, rounds)
+    }
+
+    /// Squeezes another part of the output like [`squeeze`], but applies at most `rounds`
+    /// rounds of the permutation per call.
+    ///
+    /// Returns [`Poll::Pending`] until all of `output` is written. Until then, it must be
+    /// called again with the same `output`, and no other method of the hasher may be called.
+    ///
+    /// [`squeeze`]: trait.Xof.html#tymethod.squeeze
+    /// [`Poll::Pending`]: https://doc.rust-lang.org/core/task/enum.Poll.html
+    pub fn squeeze_step(&mut self, output: &mut [u8], rounds: usize) -> Poll<()> {
+        self.state.squeeze_step(output, rounds)
+    }
 }
 
 impl Hasher for Shake {
@@ -0,0 +1,75 @@
+//! A compact, looped `keccak` permutation for size-constrained targets.
+//!
+//! Without the `small` feature, every permutation is a fully unrolled copy of its rounds. With it,
+//! all of them share [`keccak_rounds`], a single looped round function per lane width which is
+//! parameterized by the round constants.
+//!
+//! [`keccak_rounds`]: fn.keccak_rounds.html
+
+use crate::{PI, RHO, WORDS};
+use core::ops::{BitAnd, BitXor, BitXorAssign, Not};
+
+/// A lane the looped permutation can operate on.
+pub(crate) trait RoundLane:
+    Copy + BitAnd<Output = Self> + BitXor<Output = Self> + BitXorAssign + Not<Output = Self>
+{
+    const BITS: u32;
+
+    fn rotate_left(self, n: u32) -> Self;
+}
+
+macro_rules! impl_round_lane {
+    ($($lane: ty),*) => {
+        $(
+            impl RoundLane for $lane {
+                const BITS: u32 = <$lane>::BITS;
+
+                #[inline(always)]
+                fn rotate_left(self, n: u32) -> Self {
+                    <$lane>::rotate_left(self, n)
+                }
+            }
+        )*
+    };
+}
+
+impl_round_lane!(u8, u16, u32, u64);
+
+/// Applies one round of the permutation for every round constant in `rc`.
+#[inline(never)]
+pub(crate) fn keccak_rounds<L: RoundLane>(a: &mut [L; WORDS], rc: &[L]) {
+    for rc in rc {
+        let mut array = [a[0]; 5];
+
+        // Theta
+        for x in 0..5 {
+            array[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+
+        for x in 0..5 {
+            let d = array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[5 * y + x] ^= d;
+            }
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        for (pi, rho) in PI.iter().zip(RHO.iter()) {
+            let current = a[*pi];
+            a[*pi] = last.rotate_left(rho % L::BITS);
+            last = current;
+        }
+
+        // Chi
+        for y in 0..5 {
+            array.copy_from_slice(&a[5 * y..][..5]);
+            for x in 0..5 {
+                a[5 * y + x] = array[x] ^ ((!array[(x + 1) % 5]) & array[(x + 2) % 5]);
+            }
+        }
+
+        // Iota
+        a[0] ^= *rc;
+    }
+}
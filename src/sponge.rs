@@ -0,0 +1,105 @@
+//! The sponge construction defined [`here`].
+//!
+//! [`here`]: https://keccak.team/files/CSF-0.1.pdf
+
+use crate::{KeccakState, Permutation, WORDS};
+
+/// The sponge construction over an arbitrary [`Permutation`].
+///
+/// [`Sponge`] absorbs input in blocks of `rate` bytes, pads it with the caller-chosen
+/// domain separation suffix followed by the `pad10*1` rule, and squeezes an output of any
+/// length. Absorbing after squeezing starts a new absorbing phase, so the sponge can be used
+/// half-duplex.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["sponge"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{keccak_p, Permutation, Sponge};
+/// struct KeccakP6;
+///
+/// impl Permutation for KeccakP6 {
+///     type Lane = u64;
+///
+///     fn execute(state: &mut [u64; 25]) {
+///         keccak_p(state, 6);
+///     }
+/// }
+///
+/// let mut sponge = Sponge::<KeccakP6>::new(168, 0x1f);
+/// let mut output = [0u8; 32];
+/// sponge.absorb(b"hello world");
+/// sponge.squeeze(&mut output);
+/// ```
+///
+/// [`Permutation`]: trait.Permutation.html
+/// [`Sponge`]: struct.Sponge.html
+pub struct Sponge<P: Permutation> {
+    state: KeccakState<P>,
+}
+
+impl<P: Permutation> Clone for Sponge<P> {
+    fn clone(&self) -> Self {
+        Sponge {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<P: Permutation> Sponge<P> {
+    /// Creates new [`Sponge`] with a rate of `rate` bytes and the domain separation `suffix`.
+    ///
+    /// The `suffix` byte holds the domain separation bits followed by the first bit of the
+    /// padding, e.g. `0x06` for `SHA3` and `0x1f` for `SHAKE`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is `0` or greater than the size of the state, or if `suffix` is `0`,
+    /// which holds no padding bit.
+    ///
+    /// [`Sponge`]: struct.Sponge.html
+    pub fn new(rate: usize, suffix: u8) -> Sponge<P> {
+        assert!(suffix != 0, "suffix cannot be equal 0");
+        Sponge {
+            state: KeccakState::new(rate, suffix),
+        }
+    }
+
+    /// Returns the rate of the sponge in bytes.
+    pub fn rate(&self) -> usize {
+        self.state.rate
+    }
+
+    /// Returns the capacity of the sponge in bytes.
+    pub fn capacity(&self) -> usize {
+        WORDS * core::mem::size_of::<P::Lane>() - self.state.rate
+    }
+
+    /// Absorbs additional input. Can be called multiple times.
+    pub fn absorb(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Pads the absorbed input and switches the sponge to squeezing.
+    ///
+    /// Does nothing if the sponge is already squeezing.
+    pub fn pad(&mut self) {
+        self.state.switch_to_squeezing();
+    }
+
+    /// Squeezes another part of the output. Pads the absorbed input first if needed.
+    pub fn squeeze(&mut self, output: &mut [u8]) {
+        self.state.squeeze(output);
+    }
+
+    /// Resets the sponge to its initial, empty state.
+    pub fn reset(&mut self) {
+        self.state.reset();
+    }
+}
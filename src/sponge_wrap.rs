@@ -0,0 +1,351 @@
+//! The `SpongeWrap` authenticated encryption mode defined [`here`].
+//!
+//! [`here`]: https://keccak.team/files/SpongeDuplex.pdf
+
+use crate::{bits_to_rate, Duplex, InvalidTag, KeccakFNative};
+
+// frame bit followed by the first bit of the padding
+const FRAME_0: u8 = 0x02;
+const FRAME_1: u8 = 0x03;
+
+#[derive(Clone, Copy)]
+enum Phase {
+    AssociatedData,
+    Data,
+}
+
+/// The `SpongeWrap` authenticated encryption mode defined [`here`].
+///
+/// `SpongeWrap` is built on the [`Duplex`] construction over [`keccakf`] with a capacity of
+/// 256 bits. A session is started with a key and a nonce and can then wrap (encrypt and
+/// authenticate) or unwrap (decrypt and verify) a sequence of messages, each with its own
+/// associated data and tag. Every tag authenticates all the messages of the session processed so
+/// far.
+///
+/// A message can also be processed in chunks of any length: [`absorb_ad`] takes the associated
+/// data, [`encrypt_chunk`] or [`decrypt_chunk`] the data, and [`finalize_tag`] or [`verify_tag`]
+/// end the message. The result is the same as that of a single [`wrap`] or [`unwrap`] call.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["sponge_wrap"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::SpongeWrap;
+/// let key = b"secret key";
+/// let nonce = [0u8; SpongeWrap::NONCE_LEN];
+/// let plaintext = b"hello world";
+/// let mut ciphertext = [0u8; 11 + SpongeWrap::TAG_LEN];
+/// let mut decrypted = [0u8; 11];
+/// SpongeWrap::seal(key, &nonce, b"header", plaintext, &mut ciphertext);
+/// SpongeWrap::open(key, &nonce, b"header", &ciphertext, &mut decrypted).unwrap();
+/// assert_eq!(plaintext, &decrypted);
+/// ```
+///
+/// [`here`]: https://keccak.team/files/SpongeDuplex.pdf
+/// [`Duplex`]: struct.Duplex.html
+/// [`keccakf`]: fn.keccakf.html
+/// [`absorb_ad`]: struct.SpongeWrap.html#method.absorb_ad
+/// [`encrypt_chunk`]: struct.SpongeWrap.html#method.encrypt_chunk
+/// [`decrypt_chunk`]: struct.SpongeWrap.html#method.decrypt_chunk
+/// [`finalize_tag`]: struct.SpongeWrap.html#method.finalize_tag
+/// [`verify_tag`]: struct.SpongeWrap.html#method.verify_tag
+/// [`wrap`]: struct.SpongeWrap.html#method.wrap
+/// [`unwrap`]: struct.SpongeWrap.html#method.unwrap
+#[derive(Clone)]
+pub struct SpongeWrap {
+    duplex: Duplex<KeccakFNative>,
+    phase: Phase,
+    // the current block of associated data or plaintext, absorbed only once it is known whether
+    // it is the last one, since that decides its frame bit
+    block: [u8; 200],
+    len: usize,
+    // the key stream for the current block of data
+    z: [u8; 200],
+}
+
+impl SpongeWrap {
+    /// The length of the nonce in bytes.
+    pub const NONCE_LEN: usize = 16;
+
+    /// The length of the tag produced by [`seal`] in bytes.
+    ///
+    /// [`seal`]: struct.SpongeWrap.html#method.seal
+    pub const TAG_LEN: usize = 16;
+
+    /// Starts new [`SpongeWrap`] session with a security level of 128 bits.
+    ///
+    /// The key and the nonce are loaded into the state as a single key string, so the same
+    /// `(key, nonce)` pair must never be used for two sessions.
+    ///
+    /// [`SpongeWrap`]: struct.SpongeWrap.html
+    pub fn new(key: &[u8], nonce: &[u8; 16]) -> SpongeWrap {
+        let mut sponge_wrap = SpongeWrap {
+            duplex: Duplex::new(bits_to_rate(128), FRAME_0),
+            phase: Phase::AssociatedData,
+            block: [0u8; 200],
+            len: 0,
+            z: [0u8; 200],
+        };
+
+        let block_len = sponge_wrap.duplex.max_input_len();
+        let mut block = [0u8; 200];
+        let mut len = 0;
+        for byte in key.iter().chain(nonce.iter()) {
+            if len == block_len {
+                sponge_wrap
+                    .duplex
+                    .duplexing_with_suffix(&block[..len], FRAME_1, &mut []);
+                len = 0;
+            }
+            block[len] = *byte;
+            len += 1;
+        }
+        sponge_wrap
+            .duplex
+            .duplexing_with_suffix(&block[..len], FRAME_0, &mut []);
+        sponge_wrap
+    }
+
+    /// Encrypts `data` in place, authenticates it together with `ad` and writes the tag to
+    /// `tag`. The tag can be of any length.
+    pub fn wrap(&mut self, ad: &[u8], data: &mut [u8], tag: &mut [u8]) {
+        self.absorb_ad(ad);
+        self.encrypt_chunk(data);
+        self.finalize_tag(tag);
+    }
+
+    /// Decrypts `data` in place and verifies it together with `ad` against `tag` in constant
+    /// time.
+    ///
+    /// If the tag does not match, `data` is zeroed and the session must not be used anymore.
+    pub fn unwrap(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8]) -> Result<(), InvalidTag> {
+        self.absorb_ad(ad);
+        self.decrypt_chunk(data);
+        let result = self.verify_tag(tag);
+        if result.is_err() {
+            for byte in data.iter_mut() {
+                *byte = 0;
+            }
+        }
+        result
+    }
+
+    /// Appends `ad` to the associated data of the current message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if data of the current message was already encrypted or decrypted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::SpongeWrap;
+    /// let nonce = [0u8; SpongeWrap::NONCE_LEN];
+    /// let mut data = *b"hello world";
+    /// let mut tag = [0u8; 16];
+    /// let mut sponge_wrap = SpongeWrap::new(b"secret key", &nonce);
+    /// sponge_wrap.absorb_ad(b"hea");
+    /// sponge_wrap.absorb_ad(b"der");
+    /// sponge_wrap.encrypt_chunk(&mut data[..5]);
+    /// sponge_wrap.encrypt_chunk(&mut data[5..]);
+    /// sponge_wrap.finalize_tag(&mut tag);
+    ///
+    /// let mut expected = *b"hello world";
+    /// let mut expected_tag = [0u8; 16];
+    /// SpongeWrap::new(b"secret key", &nonce).wrap(b"header", &mut expected, &mut expected_tag);
+    /// assert_eq!(expected, data);
+    /// assert_eq!(expected_tag, tag);
+    /// ```
+    pub fn absorb_ad(&mut self, ad: &[u8]) {
+        assert!(
+            matches!(self.phase, Phase::AssociatedData),
+            "associated data must precede the data of a message"
+        );
+        let block_len = self.duplex.max_input_len();
+        for byte in ad {
+            if self.len == block_len {
+                self.duplex
+                    .duplexing_with_suffix(&self.block[..block_len], FRAME_0, &mut []);
+                self.len = 0;
+            }
+            self.block[self.len] = *byte;
+            self.len += 1;
+        }
+    }
+
+    /// Encrypts `data`, the next chunk of the plaintext of the current message, in place.
+    ///
+    /// The ciphertext is released at once, but authenticated only by the tag written by
+    /// [`finalize_tag`].
+    ///
+    /// [`finalize_tag`]: struct.SpongeWrap.html#method.finalize_tag
+    pub fn encrypt_chunk(&mut self, data: &mut [u8]) {
+        self.start_data();
+        for byte in data.iter_mut() {
+            self.next_data_byte();
+            self.block[self.len] = *byte;
+            *byte ^= self.z[self.len];
+            self.len += 1;
+        }
+    }
+
+    /// Decrypts `data`, the next chunk of the ciphertext of the current message, in place.
+    ///
+    /// The plaintext is not authenticated until [`verify_tag`] succeeds, so it must not be
+    /// used before.
+    ///
+    /// [`verify_tag`]: struct.SpongeWrap.html#method.verify_tag
+    pub fn decrypt_chunk(&mut self, data: &mut [u8]) {
+        self.start_data();
+        for byte in data.iter_mut() {
+            self.next_data_byte();
+            *byte ^= self.z[self.len];
+            self.block[self.len] = *byte;
+            self.len += 1;
+        }
+    }
+
+    /// Ends the current message and writes its tag to `tag`. The tag can be of any length.
+    pub fn finalize_tag(&mut self, tag: &mut [u8]) {
+        self.finish(tag.len(), |offset, chunk| {
+            tag[offset..][..chunk.len()].copy_from_slice(chunk);
+        });
+    }
+
+    /// Ends the current message and verifies it against `tag` in constant time.
+    ///
+    /// If the tag does not match, the plaintext returned by [`decrypt_chunk`] must be discarded
+    /// and the session must not be used anymore.
+    ///
+    /// [`decrypt_chunk`]: struct.SpongeWrap.html#method.decrypt_chunk
+    pub fn verify_tag(&mut self, tag: &[u8]) -> Result<(), InvalidTag> {
+        let mut diff = 0u8;
+        self.finish(tag.len(), |offset, chunk| {
+            for (a, b) in chunk.iter().zip(&tag[offset..]) {
+                diff |= a ^ b;
+            }
+        });
+
+        if diff != 0 {
+            return Err(InvalidTag);
+        }
+
+        Ok(())
+    }
+
+    /// Encrypts and authenticates `plaintext` and `ad` with a new session and writes the
+    /// ciphertext followed by a [`TAG_LEN`] bytes long tag to `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is not exactly [`TAG_LEN`] bytes longer than `plaintext`.
+    ///
+    /// [`TAG_LEN`]: struct.SpongeWrap.html#associatedconstant.TAG_LEN
+    pub fn seal(key: &[u8], nonce: &[u8; 16], ad: &[u8], plaintext: &[u8], output: &mut [u8]) {
+        assert_eq!(
+            output.len(),
+            plaintext.len() + Self::TAG_LEN,
+            "output must fit the ciphertext and the tag"
+        );
+        let (ciphertext, tag) = output.split_at_mut(plaintext.len());
+        ciphertext.copy_from_slice(plaintext);
+        SpongeWrap::new(key, nonce).wrap(ad, ciphertext, tag);
+    }
+
+    /// Decrypts and verifies the output of [`seal`] with a new session and writes the plaintext
+    /// to `output`.
+    ///
+    /// If `input` is too short to hold a tag, or the tag does not match, [`InvalidTag`] is
+    /// returned and `output` is zeroed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` holds a tag but `output` is not exactly [`TAG_LEN`] bytes shorter than
+    /// it. Unlike a truncated `input`, which anyone can send, a wrong `output` length is a bug
+    /// of the caller.
+    ///
+    /// [`InvalidTag`]: struct.InvalidTag.html
+    /// [`seal`]: struct.SpongeWrap.html#method.seal
+    /// [`TAG_LEN`]: struct.SpongeWrap.html#associatedconstant.TAG_LEN
+    pub fn open(
+        key: &[u8],
+        nonce: &[u8; 16],
+        ad: &[u8],
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), InvalidTag> {
+        if input.len() < Self::TAG_LEN {
+            for byte in output.iter_mut() {
+                *byte = 0;
+            }
+            return Err(InvalidTag);
+        }
+        assert_eq!(
+            output.len(),
+            input.len() - Self::TAG_LEN,
+            "output must fit the plaintext"
+        );
+        let (ciphertext, tag) = input.split_at(output.len());
+        output.copy_from_slice(ciphertext);
+        SpongeWrap::new(key, nonce).unwrap(ad, output, tag)
+    }
+
+    /// Absorbs the last block of associated data, if the data of the message has not started
+    /// yet, and squeezes the key stream for the first block of data.
+    fn start_data(&mut self) {
+        if let Phase::AssociatedData = self.phase {
+            let block_len = self.duplex.max_input_len();
+            self.duplex.duplexing_with_suffix(
+                &self.block[..self.len],
+                FRAME_1,
+                &mut self.z[..block_len],
+            );
+            self.phase = Phase::Data;
+            self.len = 0;
+        }
+    }
+
+    /// Absorbs the current block of plaintext once it is full, as it is not the last one.
+    fn next_data_byte(&mut self) {
+        let block_len = self.duplex.max_input_len();
+        if self.len == block_len {
+            self.duplex.duplexing_with_suffix(
+                &self.block[..block_len],
+                FRAME_1,
+                &mut self.z[..block_len],
+            );
+            self.len = 0;
+        }
+    }
+
+    fn finish<F: FnMut(usize, &[u8])>(&mut self, tag_len: usize, mut on_tag: F) {
+        self.start_data();
+        let block_len = self.duplex.max_input_len();
+        self.duplex.duplexing_with_suffix(
+            &self.block[..self.len],
+            FRAME_0,
+            &mut self.z[..block_len],
+        );
+
+        let mut offset = 0;
+        loop {
+            let len = core::cmp::min(block_len, tag_len - offset);
+            on_tag(offset, &self.z[..len]);
+            offset += len;
+            if offset == tag_len {
+                break;
+            }
+            self.duplex
+                .duplexing_with_suffix(&[], FRAME_0, &mut self.z[..block_len]);
+        }
+
+        self.phase = Phase::AssociatedData;
+        self.len = 0;
+    }
+}
@@ -0,0 +1,160 @@
+//! The step mappings of the `keccak-p[1600, n_r]` round, and a permutation tracing them.
+//!
+//! The permutations of this crate fuse the steps into one unrolled round. The functions here
+//! apply them one at a time, which is slow but lets the state be inspected after every step, as
+//! in the intermediate-value files published by the Keccak team.
+
+use crate::{round_constant, MAX_ROUNDS, PI, RHO, WORDS};
+
+/// The `theta` step: adds the parities of two neighbouring columns to every lane.
+pub fn theta(a: &mut [u64; WORDS]) {
+    let mut array = [0u64; 5];
+    for (x, column) in array.iter_mut().enumerate() {
+        *column = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+    }
+    for x in 0..5 {
+        let d = array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+        for y in 0..5 {
+            a[5 * y + x] ^= d;
+        }
+    }
+}
+
+/// The `rho` step: rotates every lane by its offset.
+pub fn rho(a: &mut [u64; WORDS]) {
+    let mut last = 1;
+    for (pi, rho) in PI.iter().zip(RHO.iter()) {
+        a[last] = a[last].rotate_left(*rho);
+        last = *pi;
+    }
+}
+
+/// The `pi` step: moves the lanes to new positions.
+pub fn pi(a: &mut [u64; WORDS]) {
+    let b = *a;
+    let mut last = 1;
+    for pi in PI.iter() {
+        a[*pi] = b[last];
+        last = *pi;
+    }
+}
+
+/// The `chi` step: the only non-linear step, applied to every row.
+pub fn chi(a: &mut [u64; WORDS]) {
+    for y in 0..5 {
+        let mut row = [0u64; 5];
+        row.copy_from_slice(&a[5 * y..][..5]);
+        for x in 0..5 {
+            a[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+        }
+    }
+}
+
+/// The `iota` step: adds the constant of round `round` of `keccak-f[1600, 24]` to the first lane.
+///
+/// `keccak-p[1600, n_r]` uses the last `n_r` rounds, so its first round is round `24 - n_r`.
+///
+/// # Panics
+///
+/// Panics if `round` is not in range `0..24`.
+pub fn iota(a: &mut [u64; WORDS], round: usize) {
+    assert!(round < MAX_ROUNDS, "round must be in range 0..24");
+    a[0] ^= round_constant(round);
+}
+
+/// A step mapping of the `keccak-p[1600, n_r]` round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// The [`theta`] step.
+    ///
+    /// [`theta`]: fn.theta.html
+    Theta,
+    /// The [`rho`] step.
+    ///
+    /// [`rho`]: fn.rho.html
+    Rho,
+    /// The [`pi`] step.
+    ///
+    /// [`pi`]: fn.pi.html
+    Pi,
+    /// The [`chi`] step.
+    ///
+    /// [`chi`]: fn.chi.html
+    Chi,
+    /// The [`iota`] step.
+    ///
+    /// [`iota`]: fn.iota.html
+    Iota,
+}
+
+impl core::fmt::Display for Step {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Step::Theta => "theta",
+            Step::Rho => "rho",
+            Step::Pi => "pi",
+            Step::Chi => "chi",
+            Step::Iota => "iota",
+        })
+    }
+}
+
+/// A sink for the intermediate states of [`keccak_p_traced`].
+///
+/// It is implemented for closures taking the same arguments as [`record`].
+///
+/// [`keccak_p_traced`]: fn.keccak_p_traced.html
+/// [`record`]: trait.Trace.html#tymethod.record
+pub trait Trace {
+    /// Records the state after step `step` of round `round`.
+    ///
+    /// Rounds are numbered as in `keccak-f[1600, 24]`, see [`iota`].
+    ///
+    /// [`iota`]: fn.iota.html
+    fn record(&mut self, round: usize, step: Step, state: &[u64; WORDS]);
+}
+
+impl<F: FnMut(usize, Step, &[u64; WORDS])> Trace for F {
+    fn record(&mut self, round: usize, step: Step, state: &[u64; WORDS]) {
+        self(round, step, state)
+    }
+}
+
+/// [`keccak_p`] computed step by step, recording the state after every step of every round.
+///
+/// # Panics
+///
+/// Panics if `rounds` is not in range `1..=24`.
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{keccak_p_traced, Step};
+/// let mut state = [0u64; 25];
+/// keccak_p_traced(&mut state, 24, &mut |round: usize, step: Step, state: &[u64; 25]| {
+///     if round == 0 && step == Step::Iota {
+///         assert_eq!(state[0], 1);
+///     }
+/// });
+/// assert_eq!(state[0], 0xf1258f7940e1dde7);
+/// ```
+///
+/// [`keccak_p`]: fn.keccak_p.html
+pub fn keccak_p_traced<T: Trace>(a: &mut [u64; WORDS], rounds: usize, trace: &mut T) {
+    assert!(
+        (1..=MAX_ROUNDS).contains(&rounds),
+        "number of rounds must be in range 1..=24"
+    );
+    for round in MAX_ROUNDS - rounds..MAX_ROUNDS {
+        theta(a);
+        trace.record(round, Step::Theta, a);
+        rho(a);
+        trace.record(round, Step::Rho, a);
+        pi(a);
+        trace.record(round, Step::Pi, a);
+        chi(a);
+        trace.record(round, Step::Chi, a);
+        iota(a, round);
+        trace.record(round, Step::Iota, a);
+    }
+}
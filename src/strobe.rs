@@ -0,0 +1,453 @@
+//! The `STROBE` protocol framework defined [`here`].
+//!
+//! [`here`]: https://strobe.sourceforge.io/specs/
+
+use crate::{keccakf::keccakf, Buffer, InvalidTag};
+
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_T: u8 = 1 << 3;
+const FLAG_M: u8 = 1 << 4;
+const FLAG_K: u8 = 1 << 5;
+
+/// The `STROBE` protocol framework, version `1.0.2`, over [`keccakf`].
+///
+/// Every operation is framed in the transcript by its flags, so the two parties of a protocol
+/// stay in sync as long as they perform matching operations: a `send_*` operation on one side
+/// corresponds to a `recv_*` operation on the other. An operation can be split across several
+/// calls by passing `more` to all but the first one. The `meta_*` variants perform the same
+/// operations on framing data rather than on the protocol data itself.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["strobe"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::Strobe;
+/// let mut alice = Strobe::v128(b"example protocol");
+/// let mut bob = Strobe::v128(b"example protocol");
+/// alice.key(b"shared secret", false);
+/// bob.key(b"shared secret", false);
+///
+/// let mut message = *b"hello world";
+/// alice.send_enc(&mut message, false);
+/// bob.recv_enc(&mut message, false);
+/// assert_eq!(b"hello world", &message);
+///
+/// let mut mac = [0u8; 16];
+/// alice.send_mac(&mut mac, false);
+/// bob.recv_mac(&mac).unwrap();
+/// ```
+///
+/// [`keccakf`]: fn.keccakf.html
+#[derive(Clone)]
+pub struct Strobe {
+    state: Buffer,
+    rate: usize,
+    pos: usize,
+    pos_begin: u8,
+    cur_flags: u8,
+    initiator: Option<u8>,
+}
+
+impl Strobe {
+    /// Creates  new [`Strobe`] object with a security level of 128 bits for the protocol
+    /// `protocol`.
+    ///
+    /// [`Strobe`]: struct.Strobe.html
+    pub fn v128(protocol: &[u8]) -> Strobe {
+        Strobe::new(protocol, 128)
+    }
+
+    /// Creates  new [`Strobe`] object with a security level of 256 bits for the protocol
+    /// `protocol`.
+    ///
+    /// [`Strobe`]: struct.Strobe.html
+    pub fn v256(protocol: &[u8]) -> Strobe {
+        Strobe::new(protocol, 256)
+    }
+
+    fn new(protocol: &[u8], bits: usize) -> Strobe {
+        let rate = 200 - bits / 4 - 2;
+        let mut state = Buffer::default();
+        let domain = [1, rate as u8 + 2, 1, 0, 1, 96];
+        state.xorin(&domain, 0, domain.len());
+        state.xorin(b"STROBEv1.0.2", domain.len(), 12);
+        keccakf(state.words());
+
+        let mut strobe = Strobe {
+            state,
+            rate,
+            pos: 0,
+            pos_begin: 0,
+            cur_flags: 0,
+            initiator: None,
+        };
+        strobe.meta_ad(protocol, false);
+        strobe
+    }
+
+    /// Absorbs associated data.
+    pub fn ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A, more);
+        self.absorb(data);
+    }
+
+    /// Absorbs associated framing data.
+    pub fn meta_ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A, more);
+        self.absorb(data);
+    }
+
+    /// Overwrites the state with a secret key.
+    pub fn key(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A | FLAG_C, more);
+        self.overwrite(data);
+    }
+
+    /// Overwrites the state with a secret framing key.
+    pub fn meta_key(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A | FLAG_C, more);
+        self.overwrite(data);
+    }
+
+    /// Fills `output` with pseudorandom data.
+    pub fn prf(&mut self, output: &mut [u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_C, more);
+        self.squeeze(output);
+    }
+
+    /// Fills `output` with pseudorandom framing data.
+    pub fn meta_prf(&mut self, output: &mut [u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_I | FLAG_A | FLAG_C, more);
+        self.squeeze(output);
+    }
+
+    /// Absorbs cleartext data that is sent to the other party.
+    pub fn send_clr(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A | FLAG_T, more);
+        self.absorb(data);
+    }
+
+    /// Absorbs cleartext framing data that is sent to the other party.
+    pub fn meta_send_clr(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A | FLAG_T, more);
+        self.absorb(data);
+    }
+
+    /// Absorbs cleartext data that is received from the other party.
+    pub fn recv_clr(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_T, more);
+        self.absorb(data);
+    }
+
+    /// Absorbs cleartext framing data that is received from the other party.
+    pub fn meta_recv_clr(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_I | FLAG_A | FLAG_T, more);
+        self.absorb(data);
+    }
+
+    /// Encrypts `data` in place before it is sent to the other party.
+    pub fn send_enc(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_A | FLAG_C | FLAG_T, more);
+        self.encrypt(data);
+    }
+
+    /// Encrypts framing `data` in place before it is sent to the other party.
+    pub fn meta_send_enc(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A | FLAG_C | FLAG_T, more);
+        self.encrypt(data);
+    }
+
+    /// Decrypts `data` received from the other party in place.
+    pub fn recv_enc(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_C | FLAG_T, more);
+        self.decrypt(data);
+    }
+
+    /// Decrypts framing `data` received from the other party in place.
+    pub fn meta_recv_enc(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_I | FLAG_A | FLAG_C | FLAG_T, more);
+        self.decrypt(data);
+    }
+
+    /// Fills `output` with a message authentication code that is sent to the other party.
+    pub fn send_mac(&mut self, output: &mut [u8], more: bool) {
+        self.begin_op(FLAG_C | FLAG_T, more);
+        self.copy(output);
+    }
+
+    /// Fills `output` with a framing message authentication code that is sent to the other
+    /// party.
+    pub fn meta_send_mac(&mut self, output: &mut [u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_C | FLAG_T, more);
+        self.copy(output);
+    }
+
+    /// Verifies a message authentication code received from the other party in constant time.
+    ///
+    /// If the code does not match, the object must not be used anymore.
+    pub fn recv_mac(&mut self, mac: &[u8]) -> Result<(), InvalidTag> {
+        self.begin_op(FLAG_I | FLAG_C | FLAG_T, false);
+        self.verify(mac)
+    }
+
+    /// Verifies a framing message authentication code received from the other party in
+    /// constant time.
+    ///
+    /// If the code does not match, the object must not be used anymore.
+    pub fn meta_recv_mac(&mut self, mac: &[u8]) -> Result<(), InvalidTag> {
+        self.begin_op(FLAG_M | FLAG_I | FLAG_C | FLAG_T, false);
+        self.verify(mac)
+    }
+
+    /// Erases `len` bytes of the state, preventing the rollback of the object to a previous
+    /// state.
+    pub fn ratchet(&mut self, len: usize, more: bool) {
+        self.begin_op(FLAG_C, more);
+        self.zero(len);
+    }
+
+    /// Erases `len` bytes of the state as a framing operation.
+    pub fn meta_ratchet(&mut self, len: usize, more: bool) {
+        self.begin_op(FLAG_M | FLAG_C, more);
+        self.zero(len);
+    }
+
+    fn run_f(&mut self) {
+        let (pos, pos_begin) = (self.pos, self.pos_begin);
+        self.state.xorin(&[pos_begin, 0x04], pos, 2);
+        self.state.xorin(&[0x80], self.rate + 1, 1);
+        keccakf(self.state.words());
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    fn begin_op(&mut self, mut flags: u8, more: bool) {
+        if more {
+            assert!(
+                flags == self.cur_flags,
+                "cannot continue an operation with different flags"
+            );
+            return;
+        }
+
+        if flags & FLAG_T != 0 {
+            let initiator = *self.initiator.get_or_insert(flags & FLAG_I);
+            flags ^= initiator;
+        }
+
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos as u8 + 1;
+        self.cur_flags = flags;
+        self.absorb(&[old_begin, flags]);
+
+        if flags & (FLAG_C | FLAG_K) != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+
+    // applies `f` to the state and the data, `rate` bytes at a time
+    fn process<F: FnMut(&mut [u8], usize)>(&mut self, len: usize, mut f: F) {
+        let mut offset = 0;
+        while offset < len {
+            let pos = self.pos;
+            let chunk = core::cmp::min(self.rate - pos, len - offset);
+            self.state.execute(pos, chunk, |state| f(state, offset));
+            self.pos += chunk;
+            offset += chunk;
+
+            if self.pos == self.rate {
+                self.run_f();
+            }
+        }
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        self.process(data.len(), |state, offset| {
+            for (s, d) in state.iter_mut().zip(&data[offset..]) {
+                *s ^= *d;
+            }
+        });
+    }
+
+    fn overwrite(&mut self, data: &[u8]) {
+        self.process(data.len(), |state, offset| {
+            let len = state.len();
+            state.copy_from_slice(&data[offset..][..len]);
+        });
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        self.process(output.len(), |state, offset| {
+            for (s, o) in state.iter_mut().zip(&mut output[offset..]) {
+                *o = *s;
+                *s = 0;
+            }
+        });
+    }
+
+    fn copy(&mut self, output: &mut [u8]) {
+        self.process(output.len(), |state, offset| {
+            let len = state.len();
+            output[offset..][..len].copy_from_slice(state);
+        });
+    }
+
+    fn zero(&mut self, len: usize) {
+        self.process(len, |state, _| {
+            for s in state.iter_mut() {
+                *s = 0;
+            }
+        });
+    }
+
+    fn encrypt(&mut self, data: &mut [u8]) {
+        self.process(data.len(), |state, offset| {
+            for (s, d) in state.iter_mut().zip(&mut data[offset..]) {
+                *s ^= *d;
+                *d = *s;
+            }
+        });
+    }
+
+    fn decrypt(&mut self, data: &mut [u8]) {
+        self.process(data.len(), |state, offset| {
+            for (s, d) in state.iter_mut().zip(&mut data[offset..]) {
+                let plaintext = *s ^ *d;
+                *s = *d;
+                *d = plaintext;
+            }
+        });
+    }
+
+    fn verify(&mut self, mac: &[u8]) -> Result<(), InvalidTag> {
+        let mut diff = 0;
+        self.process(mac.len(), |state, offset| {
+            for (s, m) in state.iter_mut().zip(&mac[offset..]) {
+                diff |= *s ^ *m;
+                *s = *m;
+            }
+        });
+
+        if diff != 0 {
+            return Err(InvalidTag);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn state_bytes(&mut self) -> [u8; 200] {
+        let mut state = [0u8; 200];
+        self.state.setout(&mut state, 0, 200);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Strobe;
+    use std::{format, vec::Vec};
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // runs a test vector of the `STROBE` reference in the JSON format of `strobe-rs`,
+    // comparing the outputs and the state after every operation
+    fn check_strobe_vector(vector: &str) {
+        fn field<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+            let value = line.trim().strip_prefix(&format!("\"{}\": ", name))?;
+            Some(value.trim_end_matches(',').trim_matches('"'))
+        }
+
+        let mut lines = vector.lines();
+        let protocol = lines.by_ref().find_map(|line| field(line, "proto_string"));
+        let mut strobe = Strobe::v128(protocol.unwrap().as_bytes());
+
+        let mut operations = Vec::new();
+        for line in lines.skip_while(|line| field(line, "operations").is_none()) {
+            if let Some(name) = field(line, "name") {
+                operations.push((name, false, "", None, "", false));
+            }
+            if let Some(op) = operations.last_mut() {
+                if let Some(meta) = field(line, "meta") {
+                    op.1 = meta == "true";
+                } else if let Some(input) = field(line, "input_data") {
+                    op.2 = input;
+                } else if let Some(output) = field(line, "output") {
+                    op.3 = Some(output);
+                } else if let Some(state) = field(line, "state_after") {
+                    op.4 = state;
+                } else if let Some(stream) = field(line, "stream") {
+                    op.5 = stream == "true";
+                }
+            }
+        }
+        assert!(operations.len() > 1);
+
+        for (i, &(name, meta, input, output, state, more)) in operations.iter().enumerate() {
+            let mut data = from_hex(input);
+            match (name, meta) {
+                ("init", _) => {}
+                ("AD", false) => strobe.ad(&data, more),
+                ("AD", true) => strobe.meta_ad(&data, more),
+                ("KEY", false) => strobe.key(&data, more),
+                ("KEY", true) => strobe.meta_key(&data, more),
+                ("PRF", false) => strobe.prf(&mut data, more),
+                ("PRF", true) => strobe.meta_prf(&mut data, more),
+                ("send_CLR", false) => strobe.send_clr(&data, more),
+                ("send_CLR", true) => strobe.meta_send_clr(&data, more),
+                ("recv_CLR", false) => strobe.recv_clr(&data, more),
+                ("recv_CLR", true) => strobe.meta_recv_clr(&data, more),
+                ("send_ENC", false) => strobe.send_enc(&mut data, more),
+                ("send_ENC", true) => strobe.meta_send_enc(&mut data, more),
+                ("recv_ENC", false) => strobe.recv_enc(&mut data, more),
+                ("recv_ENC", true) => strobe.meta_recv_enc(&mut data, more),
+                ("send_MAC", false) => strobe.send_mac(&mut data, more),
+                ("send_MAC", true) => strobe.meta_send_mac(&mut data, more),
+                // the vectors do not hold a valid tag, only the resulting state is checked
+                ("recv_MAC", false) => assert!(strobe.recv_mac(&data).is_err()),
+                ("recv_MAC", true) => assert!(strobe.meta_recv_mac(&data).is_err()),
+                ("RATCHET", false) => strobe.ratchet(data.len(), more),
+                ("RATCHET", true) => strobe.meta_ratchet(data.len(), more),
+                _ => panic!("unknown operation {}", name),
+            }
+
+            if let Some(output) = output {
+                assert_eq!(from_hex(output), data, "operation {} ({})", i, name);
+            }
+            let expected = from_hex(state);
+            assert_eq!(
+                &expected[..],
+                &strobe.state_bytes()[..],
+                "operation {} ({})",
+                i,
+                name
+            );
+        }
+    }
+
+    // the test vectors of `strobe-rs`, taken from `StrobeGo`
+    #[test]
+    fn test_strobe_reference_vectors() {
+        check_strobe_vector(include_str!("../tests/strobe_kat/simple_test_vector.json"));
+        check_strobe_vector(include_str!("../tests/strobe_kat/meta_test_vector.json"));
+        check_strobe_vector(include_str!(
+            "../tests/strobe_kat/streaming_test_vector.json"
+        ));
+        check_strobe_vector(include_str!(
+            "../tests/strobe_kat/boundary_test_vector.json"
+        ));
+    }
+}
@@ -0,0 +1,436 @@
+//! `keccak-f[1600]` and `keccak-p[1600, 12]` applied to several independent states at once.
+//!
+//! The states are interleaved lane by lane: `a[i][j]` is the lane `i` of the state `j`, so
+//! every step of the round operates on `N` adjacent words and maps to SIMD instructions. On
+//! `x86_64` an `AVX2` implementation permuting 4 states at a time is selected at runtime for 4
+//! and 8 states; 2 states never use SIMD. Everywhere else, and on CPUs without `AVX2`, a
+//! portable implementation producing the same output is used.
+
+use crate::{MAX_ROUNDS, PI, RC, RHO, WORDS};
+
+const KECCAKP_ROUNDS: usize = 12;
+
+pub(crate) fn keccak_portable<const N: usize>(a: &mut [[u64; N]; WORDS], rc: &[u64]) {
+    for rc in rc {
+        let mut array = [[0u64; N]; 5];
+
+        // Theta
+        for x in 0..5 {
+            for y in 0..5 {
+                for j in 0..N {
+                    array[x][j] ^= a[5 * y + x][j];
+                }
+            }
+        }
+
+        for x in 0..5 {
+            for j in 0..N {
+                let d = array[(x + 4) % 5][j] ^ array[(x + 1) % 5][j].rotate_left(1);
+                for y in 0..5 {
+                    a[5 * y + x][j] ^= d;
+                }
+            }
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        for x in 0..24 {
+            let current = a[PI[x]];
+            for j in 0..N {
+                a[PI[x]][j] = last[j].rotate_left(RHO[x]);
+            }
+            last = current;
+        }
+
+        // Chi
+        for y in 0..5 {
+            array.copy_from_slice(&a[5 * y..][..5]);
+            for x in 0..5 {
+                for j in 0..N {
+                    a[5 * y + x][j] =
+                        array[x][j] ^ ((!array[(x + 1) % 5][j]) & array[(x + 2) % 5][j]);
+                }
+            }
+        }
+
+        // Iota
+        for lane in a[0].iter_mut() {
+            *lane ^= rc;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod avx2 {
+    use crate::{PI, RHO, WORDS};
+    use core::arch::x86_64::*;
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    const UNKNOWN: u8 = 0;
+    const AVAILABLE: u8 = 1;
+    const UNAVAILABLE: u8 = 2;
+
+    static SUPPORT: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+    fn detect() -> bool {
+        // `is_x86_feature_detected!` requires `std`, so query `cpuid` directly
+        unsafe {
+            let leaf1 = __cpuid(1);
+            let osxsave = leaf1.ecx & (1 << 27) != 0;
+            let avx = leaf1.ecx & (1 << 28) != 0;
+            if !osxsave || !avx || __get_cpuid_max(0).0 < 7 {
+                return false;
+            }
+            // the OS must save the `xmm` and `ymm` registers on context switches
+            if xgetbv() & 0b110 != 0b110 {
+                return false;
+            }
+            __cpuid_count(7, 0).ebx & (1 << 5) != 0
+        }
+    }
+
+    #[target_feature(enable = "xsave")]
+    unsafe fn xgetbv() -> u64 {
+        _xgetbv(0)
+    }
+
+    pub(super) fn is_available() -> bool {
+        match SUPPORT.load(Ordering::Relaxed) {
+            AVAILABLE => true,
+            UNAVAILABLE => false,
+            _ => {
+                let available = detect();
+                let support = if available { AVAILABLE } else { UNAVAILABLE };
+                SUPPORT.store(support, Ordering::Relaxed);
+                available
+            }
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn rotate_left(x: __m256i, n: u32) -> __m256i {
+        let n = n as i64;
+        _mm256_or_si256(
+            _mm256_sllv_epi64(x, _mm256_set1_epi64x(n)),
+            _mm256_srlv_epi64(x, _mm256_set1_epi64x(64 - n)),
+        )
+    }
+
+    /// Applies the rounds `rc` to the states `offset..offset + 4` of `a`.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support `AVX2`.
+    #[target_feature(enable = "avx2")]
+    pub(crate) unsafe fn keccak<const N: usize>(
+        a: &mut [[u64; N]; WORDS],
+        offset: usize,
+        rc: &[u64],
+    ) {
+        assert!(offset + 4 <= N);
+        let mut s = [_mm256_setzero_si256(); WORDS];
+        for i in 0..WORDS {
+            s[i] = _mm256_loadu_si256(a[i][offset..].as_ptr() as *const __m256i);
+        }
+
+        for rc in rc {
+            let mut array = [_mm256_setzero_si256(); 5];
+
+            // Theta
+            for x in 0..5 {
+                array[x] = _mm256_xor_si256(
+                    _mm256_xor_si256(s[x], s[x + 5]),
+                    _mm256_xor_si256(_mm256_xor_si256(s[x + 10], s[x + 15]), s[x + 20]),
+                );
+            }
+
+            for x in 0..5 {
+                let d = _mm256_xor_si256(array[(x + 4) % 5], rotate_left(array[(x + 1) % 5], 1));
+                for y in 0..5 {
+                    s[5 * y + x] = _mm256_xor_si256(s[5 * y + x], d);
+                }
+            }
+
+            // Rho and pi
+            let mut last = s[1];
+            for x in 0..24 {
+                let current = s[PI[x]];
+                s[PI[x]] = rotate_left(last, RHO[x]);
+                last = current;
+            }
+
+            // Chi
+            for y in 0..5 {
+                array.copy_from_slice(&s[5 * y..][..5]);
+                for x in 0..5 {
+                    s[5 * y + x] = _mm256_xor_si256(
+                        array[x],
+                        _mm256_andnot_si256(array[(x + 1) % 5], array[(x + 2) % 5]),
+                    );
+                }
+            }
+
+            // Iota
+            s[0] = _mm256_xor_si256(s[0], _mm256_set1_epi64x(*rc as i64));
+        }
+
+        for i in 0..WORDS {
+            _mm256_storeu_si256(a[i][offset..].as_mut_ptr() as *mut __m256i, s[i]);
+        }
+    }
+}
+
+fn keccak_times<const N: usize>(a: &mut [[u64; N]; WORDS], rc: &[u64]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // the vectorized implementation permutes 4 states at a time, so 2 states always take
+        // the portable path
+        if N & 3 == 0 && avx2::is_available() {
+            for offset in (0..N).step_by(4) {
+                unsafe { avx2::keccak(a, offset, rc) };
+            }
+            return;
+        }
+    }
+
+    keccak_portable(a, rc);
+}
+
+macro_rules! keccak_times_function {
+    ($doc: expr, $name: ident, $n: expr, $rounds: expr) => {
+        #[doc = $doc]
+        pub fn $name(a: &mut [[u64; $n]; WORDS]) {
+            keccak_times(a, &RC[MAX_ROUNDS - $rounds..]);
+        }
+    };
+}
+
+keccak_times_function!(
+    "`keccak-f[1600]` applied to 2 interleaved states, always without SIMD.",
+    keccakf_times2,
+    2,
+    MAX_ROUNDS
+);
+keccak_times_function!(
+    "`keccak-f[1600]` applied to 4 interleaved states.",
+    keccakf_times4,
+    4,
+    MAX_ROUNDS
+);
+keccak_times_function!(
+    "`keccak-f[1600]` applied to 8 interleaved states.",
+    keccakf_times8,
+    8,
+    MAX_ROUNDS
+);
+keccak_times_function!(
+    "`keccak-p[1600, 12]` applied to 2 interleaved states, always without SIMD.",
+    keccakp_times2,
+    2,
+    KECCAKP_ROUNDS
+);
+keccak_times_function!(
+    "`keccak-p[1600, 12]` applied to 4 interleaved states.",
+    keccakp_times4,
+    4,
+    KECCAKP_ROUNDS
+);
+keccak_times_function!(
+    "`keccak-p[1600, 12]` applied to 8 interleaved states.",
+    keccakp_times8,
+    8,
+    KECCAKP_ROUNDS
+);
+
+/// A permutation whose rounds can be applied to several interleaved states at once.
+#[cfg(any(
+    feature = "keccak",
+    feature = "sha3",
+    feature = "shake",
+    feature = "k12",
+    feature = "parallel_hash"
+))]
+// a `small` build hashes the inputs one at a time with `Permutation::execute`
+#[cfg_attr(feature = "small", allow(dead_code))]
+pub(crate) trait PermutationTimes: crate::Permutation<Lane = u64> {
+    /// The number of the last rounds of `keccak-f[1600]` applied by the permutation.
+    const ROUNDS: usize;
+
+    /// Applies the permutation to `N` interleaved states.
+    fn execute_times<const N: usize>(a: &mut [[u64; N]; WORDS]) {
+        keccak_times(a, &RC[MAX_ROUNDS - Self::ROUNDS..]);
+    }
+}
+
+/// Hashes every input with an independent sponge and writes the result to the corresponding
+/// output, running up to 8 sponges in lock-step.
+///
+/// Consecutive inputs with the same number of whole blocks are processed together, so
+/// batches of equally long inputs get the most out of it. All outputs must be equally long.
+/// With the `small` feature, the inputs are hashed one at a time by `Permutation::execute`.
+#[cfg(any(
+    feature = "keccak",
+    feature = "sha3",
+    feature = "shake",
+    feature = "k12",
+    feature = "parallel_hash"
+))]
+pub(crate) fn sponge_batch<P, I, O>(rate: usize, delim: u8, inputs: &[I], outputs: &mut [O])
+where
+    P: PermutationTimes,
+    I: AsRef<[u8]>,
+    O: AsMut<[u8]>,
+{
+    assert_eq!(
+        inputs.len(),
+        outputs.len(),
+        "number of inputs and outputs must be equal"
+    );
+    // checked before any output is written
+    if let Some((first, rest)) = outputs.split_first_mut() {
+        let output_len = first.as_mut().len();
+        for output in rest {
+            assert_eq!(
+                output.as_mut().len(),
+                output_len,
+                "all outputs must be equally long"
+            );
+        }
+    }
+
+    // the multi-state permutations are not worth their size in a `small` build, so the
+    // inputs go through the same `P::execute` as every other sponge
+    #[cfg(feature = "small")]
+    for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+        let mut state = crate::KeccakState::<P>::new(rate, delim);
+        state.update(input.as_ref());
+        state.finalize(output.as_mut());
+    }
+
+    #[cfg(not(feature = "small"))]
+    let mut done = 0;
+    #[cfg(not(feature = "small"))]
+    while done < inputs.len() {
+        let inputs = &inputs[done..];
+        let outputs = &mut outputs[done..];
+        let blocks = inputs[0].as_ref().len() / rate;
+        let lock_step = inputs
+            .iter()
+            .take(8)
+            .take_while(|input| input.as_ref().len() / rate == blocks)
+            .count();
+
+        done += match lock_step {
+            8 => sponge_times::<P, I, O, 8>(rate, delim, inputs, outputs),
+            4..=7 => sponge_times::<P, I, O, 4>(rate, delim, inputs, outputs),
+            2..=3 => sponge_times::<P, I, O, 2>(rate, delim, inputs, outputs),
+            _ => sponge_times::<P, I, O, 1>(rate, delim, inputs, outputs),
+        };
+    }
+}
+
+/// Hashes the consecutive `len`-byte pieces of `input` like [`sponge_batch`], spreading groups
+/// of 8 pieces across the `rayon` thread pool.
+#[cfg(all(feature = "parallel", any(feature = "k12", feature = "parallel_hash")))]
+pub(crate) fn sponge_batch_parallel<P: PermutationTimes>(
+    rate: usize,
+    delim: u8,
+    input: &[u8],
+    len: usize,
+    outputs: &mut [[u8; 64]],
+) {
+    use rayon::prelude::*;
+
+    assert_eq!(
+        input.len(),
+        len * outputs.len(),
+        "input must consist of one piece per output"
+    );
+    outputs
+        .par_chunks_mut(8)
+        .zip(input.par_chunks(8 * len))
+        .for_each(|(outputs, group)| {
+            let mut inputs: [&[u8]; 8] = Default::default();
+            for (dst, piece) in inputs.iter_mut().zip(group.chunks(len)) {
+                *dst = piece;
+            }
+            sponge_batch::<P, _, _>(rate, delim, &inputs[..outputs.len()], outputs);
+        });
+}
+
+#[cfg(all(
+    not(feature = "small"),
+    any(
+        feature = "keccak",
+        feature = "sha3",
+        feature = "shake",
+        feature = "k12",
+        feature = "parallel_hash"
+    )
+))]
+fn sponge_times<P, I, O, const N: usize>(
+    rate: usize,
+    delim: u8,
+    inputs: &[I],
+    outputs: &mut [O],
+) -> usize
+where
+    P: PermutationTimes,
+    I: AsRef<[u8]>,
+    O: AsMut<[u8]>,
+{
+    use core::convert::TryInto;
+
+    debug_assert!(rate & 7 == 0);
+    let output_len = outputs[0].as_mut().len();
+    for output in outputs[..N].iter_mut() {
+        assert_eq!(
+            output.as_mut().len(),
+            output_len,
+            "all outputs must be equally long"
+        );
+    }
+
+    let mut state = [[0u64; N]; WORDS];
+    let blocks = inputs[0].as_ref().len() / rate;
+    for block in 0..blocks {
+        for (j, input) in inputs[..N].iter().enumerate() {
+            let bytes = &input.as_ref()[block * rate..][..rate];
+            for (i, lane) in bytes.chunks_exact(8).enumerate() {
+                state[i][j] ^= u64::from_le_bytes(lane.try_into().unwrap());
+            }
+        }
+        P::execute_times(&mut state);
+    }
+
+    for (j, input) in inputs[..N].iter().enumerate() {
+        let remainder = &input.as_ref()[blocks * rate..];
+        let mut block = [0u8; WORDS * 8];
+        block[..remainder.len()].copy_from_slice(remainder);
+        block[remainder.len()] ^= delim;
+        block[rate - 1] ^= 0x80;
+        for (i, lane) in block[..rate].chunks_exact(8).enumerate() {
+            state[i][j] ^= u64::from_le_bytes(lane.try_into().unwrap());
+        }
+    }
+    P::execute_times(&mut state);
+
+    let mut offset = 0;
+    loop {
+        for (j, output) in outputs[..N].iter_mut().enumerate() {
+            let output = output.as_mut();
+            let len = core::cmp::min(rate, output_len - offset);
+            for (i, chunk) in output[offset..][..len].chunks_mut(8).enumerate() {
+                chunk.copy_from_slice(&state[i][j].to_le_bytes()[..chunk.len()]);
+            }
+        }
+
+        offset += rate;
+        if offset >= output_len {
+            break;
+        }
+        P::execute_times(&mut state);
+    }
+
+    N
+}
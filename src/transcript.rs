@@ -0,0 +1,101 @@
+//! A Fiat-Shamir transcript built on top of `TupleHash`.
+
+use crate::{Hasher, IntoXof, TupleHash, Xof};
+
+// the first element of every operation
+const MESSAGE: &[u8] = &[0x01];
+const CHALLENGE: &[u8] = &[0x02];
+const FORK: &[u8] = &[0x03];
+
+/// A Fiat-Shamir transcript built on top of [`TupleHash`].
+///
+/// Every operation appends three elements to the underlying tuple: the kind of the operation,
+/// its label and its data. Since `TupleHash` encodes each element with its length, the
+/// transcript is unambiguous regardless of how the messages are serialized. The label of
+/// the transcript itself is used as the customization string, so transcripts of different
+/// protocols are domain separated.
+///
+/// Challenges are squeezed from [`TupleHashXof`] over the whole transcript, including the
+/// challenge operation itself, so consecutive challenges are independent.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["transcript"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::Transcript;
+/// let mut transcript = Transcript::v128(b"example protocol");
+/// transcript.append_message(b"commitment", b"hello world");
+/// transcript.append_u64(b"round", 1);
+///
+/// let mut challenge = [0u8; 32];
+/// transcript.challenge_bytes(b"challenge", &mut challenge);
+/// ```
+///
+/// [`TupleHash`]: struct.TupleHash.html
+/// [`TupleHashXof`]: struct.TupleHashXof.html
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: TupleHash,
+}
+
+impl Transcript {
+    /// Creates  new [`Transcript`] with a security level of 128 bits for the protocol `label`.
+    ///
+    /// [`Transcript`]: struct.Transcript.html
+    pub fn v128(label: &[u8]) -> Transcript {
+        Transcript {
+            hasher: TupleHash::v128(label),
+        }
+    }
+
+    /// Creates  new [`Transcript`] with a security level of 256 bits for the protocol `label`.
+    ///
+    /// [`Transcript`]: struct.Transcript.html
+    pub fn v256(label: &[u8]) -> Transcript {
+        Transcript {
+            hasher: TupleHash::v256(label),
+        }
+    }
+
+    /// Appends `message` to the transcript.
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.append(MESSAGE, label, message);
+    }
+
+    /// Appends `value` to the transcript as an 8-byte little-endian message.
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &value.to_le_bytes());
+    }
+
+    /// Fills `output` with a challenge bound to the transcript so far.
+    ///
+    /// The length of `output` is appended to the transcript, so challenges of different
+    /// lengths are unrelated.
+    pub fn challenge_bytes(&mut self, label: &[u8], output: &mut [u8]) {
+        self.append(CHALLENGE, label, &(output.len() as u64).to_le_bytes());
+        self.hasher.clone().into_xof().squeeze(output);
+    }
+
+    /// Derives a new transcript from this one.
+    ///
+    /// Both the fork and this transcript record the fork, each from its own side, so they
+    /// produce independent challenges from then on.
+    pub fn fork(&mut self, label: &[u8]) -> Transcript {
+        let mut fork = self.clone();
+        fork.append(FORK, label, &[1]);
+        self.append(FORK, label, &[0]);
+        fork
+    }
+
+    fn append(&mut self, kind: &[u8], label: &[u8], data: &[u8]) {
+        self.hasher.update(kind);
+        self.hasher.update(label);
+        self.hasher.update(data);
+    }
+}
@@ -0,0 +1,89 @@
+//! The `TurboSHAKE` extendable-output functions defined in [`RFC 9861`].
+//!
+//! [`RFC 9861`]: https://www.rfc-editor.org/rfc/rfc9861
+
+use crate::{bits_to_rate, keccakp::KeccakP, Hasher, KeccakState, Xof};
+
+/// The `TurboSHAKE` extendable-output functions defined in [`RFC 9861`].
+///
+/// `TurboSHAKE` is `SHAKE` built on top of [`keccakp`], the 12-round `Keccak-p[1600]`
+/// permutation, with a caller-chosen domain separation byte.
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["turbo_shake"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use tiny_keccak::{TurboShake, Xof, Hasher};
+/// let input = b"hello world";
+/// let mut output = [0u8; 64];
+/// let mut turbo_shake = TurboShake::v128(TurboShake::DEFAULT_DOMAIN);
+/// turbo_shake.update(input);
+/// turbo_shake.squeeze(&mut output[..32]);
+/// turbo_shake.squeeze(&mut output[32..]);
+/// ```
+///
+/// [`RFC 9861`]: https://www.rfc-editor.org/rfc/rfc9861
+/// [`keccakp`]: fn.keccakp.html
+#[derive(Clone)]
+pub struct TurboShake {
+    state: KeccakState<KeccakP>,
+}
+
+impl TurboShake {
+    /// The domain separation byte recommended for general use.
+    pub const DEFAULT_DOMAIN: u8 = 0x1f;
+
+    /// Creates  new [`TurboShake`] hasher with a security level of 128 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain` is not in range `0x01..=0x7f`.
+    ///
+    /// [`TurboShake`]: struct.TurboShake.html
+    pub fn v128(domain: u8) -> TurboShake {
+        TurboShake::new(domain, 128)
+    }
+
+    /// Creates  new [`TurboShake`] hasher with a security level of 256 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain` is not in range `0x01..=0x7f`.
+    ///
+    /// [`TurboShake`]: struct.TurboShake.html
+    pub fn v256(domain: u8) -> TurboShake {
+        TurboShake::new(domain, 256)
+    }
+
+    pub(crate) fn new(domain: u8, bits: usize) -> TurboShake {
+        assert!(
+            (0x01..=0x7f).contains(&domain),
+            "domain separation byte must be in range 0x01..=0x7f"
+        );
+        TurboShake {
+            state: KeccakState::new(bits_to_rate(bits), domain),
+        }
+    }
+}
+
+impl Hasher for TurboShake {
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+}
+
+impl Xof for TurboShake {
+    fn squeeze(&mut self, output: &mut [u8]) {
+        self.state.squeeze(output)
+    }
+}
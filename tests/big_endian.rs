@@ -0,0 +1,134 @@
+//! Known answers absorbed and squeezed in pieces of every alignment, so that a lane stored in
+//! the wrong byte order cannot go unnoticed. Meant to be run on a big-endian target, e.g.
+//!
+//! ```sh
+//! cargo +nightly miri test --target s390x-unknown-linux-gnu --test big_endian \
+//!     --features sha3,shake,sponge,keccak,keccakf200,keccakf400,keccakf800
+//! ```
+//!
+//! On a 32-bit target such as `powerpc-unknown-linux-gnu` or `mips-unknown-linux-gnu`, `Sha3`
+//! and `Shake` use the bit-interleaved permutation.
+
+use tiny_keccak::{
+    Hasher, Keccak, KeccakF200, KeccakF400, KeccakF800, Permutation, Sha3, Shake, Sponge, Xof,
+};
+
+const CHUNKS: [usize; 6] = [1, 3, 5, 8, 13, 200];
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + 1) as u8).collect()
+}
+
+fn update_chunked<H: Hasher>(hasher: &mut H, input: &[u8], chunk: usize) {
+    for piece in input.chunks(chunk) {
+        hasher.update(piece);
+    }
+}
+
+#[test]
+fn sha3_256_chunked() {
+    let expected = b"\
+        \x79\xf3\x8a\xde\xc5\xc2\x03\x07\xa9\x8e\xf7\x6e\x83\x24\xaf\xbf\
+        \xd4\x6c\xfd\x81\xb2\x2e\x39\x73\xc6\x5f\xa1\xbd\x9d\xe3\x17\x87\
+    ";
+    for &chunk in CHUNKS.iter() {
+        let mut sha3 = Sha3::v256();
+        let mut output = [0u8; 32];
+        update_chunked(&mut sha3, &[0xa3; 200], chunk);
+        sha3.finalize(&mut output);
+        assert_eq!(expected, &output, "chunk {}", chunk);
+    }
+}
+
+#[test]
+fn sha3_224_unaligned_padding() {
+    let expected = b"\
+        \xe6\x42\x82\x4c\x3f\x8c\xf2\x4a\xd0\x92\x34\xee\x7d\x3c\x76\x6f\
+        \xc9\xa3\xa5\x16\x8d\x0c\x94\xad\x73\xb4\x6f\xdf\
+    ";
+    let mut sha3 = Sha3::v224();
+    let mut output = [0u8; 28];
+    sha3.update(b"abc");
+    sha3.finalize(&mut output);
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn keccak_256_empty() {
+    let expected = b"\
+        \xc5\xd2\x46\x01\x86\xf7\x23\x3c\x92\x7e\x7d\xb2\xdc\xc7\x03\xc0\
+        \xe5\x00\xb6\x53\xca\x82\x27\x3b\x7b\xfa\xd8\x04\x5d\x85\xa4\x70\
+    ";
+    let keccak = Keccak::v256();
+    let mut output = [0u8; 32];
+    keccak.finalize(&mut output);
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn shake_128_squeezed_chunked() {
+    let expected = b"\
+        \x7f\x9c\x2b\xa4\xe8\x8f\x82\x7d\x61\x60\x45\x50\x76\x05\x85\x3e\
+        \xd7\x3b\x80\x93\xf6\xef\xbc\x88\xeb\x1a\x6e\xac\xfa\x66\xef\x26\
+    ";
+    let mut reference = [0u8; 400];
+    Shake::v128().squeeze(&mut reference);
+    assert_eq!(expected, &reference[..32]);
+
+    for &chunk in CHUNKS.iter() {
+        let mut shake = Shake::v128();
+        let mut output = [0u8; 400];
+        for piece in output.chunks_mut(chunk) {
+            shake.squeeze(piece);
+        }
+        assert_eq!(&reference[..], &output[..], "chunk {}", chunk);
+    }
+}
+
+fn test_narrow_lanes<P: Permutation>(rate: usize, expected: &[u8; 48]) {
+    let input = pattern(150);
+    for &chunk in CHUNKS.iter() {
+        let mut sponge = Sponge::<P>::new(rate, 0x1f);
+        for piece in input.chunks(chunk) {
+            sponge.absorb(piece);
+        }
+        let mut output = [0u8; 48];
+        for piece in output.chunks_mut(chunk) {
+            sponge.squeeze(piece);
+        }
+        assert_eq!(expected, &output, "chunk {}", chunk);
+    }
+}
+
+#[test]
+fn sponge_keccakf200_chunked() {
+    let expected = [
+        0xc1, 0x67, 0x9f, 0x65, 0x16, 0xd0, 0xba, 0x7a, 0x8a, 0x86, 0x19, 0xc1, 0x62, 0x52, 0xd5,
+        0xa0, 0x24, 0x20, 0x0d, 0x0a, 0xb0, 0x92, 0xd2, 0x0a, 0x69, 0xa4, 0x46, 0x77, 0xce, 0xd0,
+        0x61, 0x91, 0x1b, 0x74, 0xd0, 0x46, 0x9d, 0x1a, 0x93, 0x35, 0xe6, 0x67, 0xd9, 0x31, 0xe9,
+        0x77, 0xd6, 0x70,
+    ];
+    test_narrow_lanes::<KeccakF200>(18, &expected);
+}
+
+#[test]
+fn sponge_keccakf400_chunked() {
+    let expected = [
+        0x60, 0xee, 0x1f, 0x9d, 0xf4, 0x14, 0xe7, 0x2a, 0x27, 0xe6, 0xb6, 0xbc, 0xbb, 0x29, 0x1c,
+        0x4e, 0xad, 0x86, 0xb6, 0xa9, 0xdb, 0x47, 0xb8, 0xd5, 0x41, 0xcc, 0x23, 0xe5, 0x66, 0xca,
+        0x95, 0xf1, 0x41, 0x70, 0x30, 0x1d, 0xaa, 0x9b, 0x11, 0xf6, 0x15, 0xba, 0x22, 0x0a, 0x5c,
+        0xd8, 0xb1, 0xe5,
+    ];
+    test_narrow_lanes::<KeccakF400>(38, &expected);
+}
+
+#[test]
+fn sponge_keccakf800_chunked() {
+    let expected = [
+        0x53, 0x85, 0xc6, 0xd2, 0x5d, 0xd3, 0x3c, 0x93, 0x94, 0x2e, 0xfb, 0xaf, 0xf0, 0x78, 0xfd,
+        0x72, 0xc5, 0x4b, 0x95, 0x10, 0x55, 0xd2, 0xd0, 0x1a, 0xbe, 0x36, 0xe5, 0xdc, 0x2a, 0xb6,
+        0x3e, 0x20, 0x23, 0xf1, 0x2e, 0xd8, 0x56, 0x47, 0x72, 0xba, 0x11, 0x9b, 0x82, 0x42, 0x17,
+        0x7a, 0x46, 0x12,
+    ];
+    test_narrow_lanes::<KeccakF800>(70, &expected);
+}
@@ -0,0 +1,73 @@
+use tiny_keccak::{Duplex, KeccakF, KeccakP, Sponge};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn padded(block: &[u8], suffix: u8, rate: usize) -> Vec<u8> {
+    let mut padded = block.to_vec();
+    padded.resize(rate, 0);
+    padded[block.len()] ^= suffix;
+    padded[rate - 1] ^= 0x80;
+    padded
+}
+
+// The output of each duplexing call equals the output of the sponge over all previous
+// padded input blocks followed by the current one.
+fn test_duplex_matches_sponge(rate: usize, suffix: u8, lens: &[(usize, usize)]) {
+    let mut duplex = Duplex::<KeccakF>::new(rate, suffix);
+    let mut absorbed = Vec::new();
+
+    for &(input_len, output_len) in lens {
+        let input = pattern(input_len);
+        let mut output = vec![0u8; output_len];
+        duplex.duplexing(&input, &mut output);
+
+        let mut sponge = Sponge::<KeccakF>::new(rate, suffix);
+        let mut expected = vec![0u8; output_len];
+        sponge.absorb(&absorbed);
+        sponge.absorb(&input);
+        sponge.squeeze(&mut expected);
+        assert_eq!(expected, output);
+
+        absorbed.extend(padded(&input, suffix, rate));
+    }
+}
+
+#[test]
+fn duplex_matches_sponge() {
+    test_duplex_matches_sponge(166, 0x01, &[(0, 0), (1, 166), (165, 32), (17, 1), (0, 166)]);
+}
+
+#[test]
+fn duplex_matches_sponge_with_suffix() {
+    test_duplex_matches_sponge(136, 0x06, &[(135, 136), (3, 64), (0, 10)]);
+}
+
+#[test]
+fn duplex_rate_and_capacity() {
+    let duplex = Duplex::<KeccakP>::new(166, 0x01);
+    assert_eq!(duplex.rate(), 166);
+    assert_eq!(duplex.capacity(), 34);
+    assert_eq!(duplex.max_input_len(), 165);
+}
+
+#[test]
+#[should_panic]
+fn duplex_input_too_long() {
+    let mut duplex = Duplex::<KeccakF>::new(166, 0x01);
+    duplex.duplexing(&[0u8; 166], &mut []);
+}
+
+#[test]
+#[should_panic]
+fn duplex_output_too_long() {
+    let mut duplex = Duplex::<KeccakF>::new(166, 0x01);
+    duplex.duplexing(&[], &mut [0u8; 167]);
+}
+
+#[test]
+#[should_panic(expected = "suffix cannot be equal 0")]
+fn duplex_zero_suffix() {
+    Duplex::<KeccakF>::new(166, 0x00);
+}
@@ -1,4 +1,4 @@
-use tiny_keccak::{Hasher, KangarooTwelve};
+use tiny_keccak::{Hasher, IntoXof, KangarooTwelve, KangarooTwelve256, Xof};
 
 fn pattern(len: usize) -> Vec<u8> {
     (0..len).map(|j| (j % 251) as u8).collect()
@@ -63,6 +63,7 @@ fn kangaroo_twelve_with_custom_string() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn kangaroo_twelve_with_custom_string_and_message() {
     let expected = b"\
         \xd8\x48\xc5\x06\x8c\xed\x73\x6f\x44\x62\x15\x9b\x98\x67\xfd\x4c\
@@ -72,6 +73,7 @@ fn kangaroo_twelve_with_custom_string_and_message() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn kangaroo_twelve_with_custom_string_and_message2() {
     let expected = b"\
         \x75\xd2\xf8\x6a\x2e\x64\x45\x66\x72\x6b\x4f\xbc\xfc\x56\x57\xb9\
@@ -84,3 +86,190 @@ fn kangaroo_twelve_with_custom_string_and_message2() {
         expected,
     );
 }
+
+fn test_kangaroo_twelve256<A: AsRef<[u8]>, B: AsRef<[u8]>>(
+    custom_string: A,
+    message: B,
+    output_len: usize,
+    expected: &[u8],
+) {
+    let mut kangaroo = KangarooTwelve256::new(custom_string.as_ref());
+    kangaroo.update(message.as_ref());
+    let mut res = vec![0; output_len];
+    kangaroo.finalize(&mut res);
+    assert_eq!(&res[output_len - expected.len()..], expected);
+}
+
+#[test]
+fn empty_kangaroo_twelve256() {
+    let expected = b"\
+        \xb2\x3d\x2e\x9c\xea\x9f\x49\x04\xe0\x2b\xec\x06\x81\x7f\xc1\x0c\
+        \xe3\x8c\xe8\xe9\x3e\xf4\xc8\x9e\x65\x37\x07\x6a\xf8\x64\x64\x04\
+        \xe3\xe8\xb6\x81\x07\xb8\x83\x3a\x5d\x30\x49\x0a\xa3\x34\x82\x35\
+        \x3f\xd4\xad\xc7\x14\x8e\xcb\x78\x28\x55\x00\x3a\xae\xbd\xe4\xa9\
+    ";
+    test_kangaroo_twelve256("", "", 64, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_long() {
+    let expected = b"\
+        \xad\x4a\x1d\x71\x8c\xf9\x50\x50\x67\x09\xa4\xc3\x33\x96\x13\x9b\
+        \x44\x49\x04\x1f\xc7\x9a\x05\xd6\x8d\xa3\x5f\x1e\x45\x35\x22\xe0\
+        \x56\xc6\x4f\xe9\x49\x58\xe7\x08\x5f\x29\x64\x88\x82\x59\xb9\x93\
+        \x27\x52\xf3\xcc\xd8\x55\x28\x8e\xfe\xe5\xfc\xbb\x8b\x56\x30\x69\
+    ";
+    test_kangaroo_twelve256("", "", 10064, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_with_message() {
+    let expected = b"\
+        \x0d\x00\x5a\x19\x40\x85\x36\x02\x17\x12\x8c\xf1\x7f\x91\xe1\xf7\
+        \x13\x14\xef\xa5\x56\x45\x39\xd4\x44\x91\x2e\x34\x37\xef\xa1\x7f\
+        \x82\xdb\x6f\x6f\xfe\x76\xe7\x81\xea\xa0\x68\xbc\xe0\x1f\x2b\xbf\
+        \x81\xea\xcb\x98\x3d\x72\x30\xf2\xfb\x02\x83\x4a\x21\xb1\xdd\xd0\
+    ";
+    test_kangaroo_twelve256("", pattern(1), 64, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_with_message2() {
+    let expected = b"\
+        \x1b\xa3\xc0\x2b\x1f\xc5\x14\x47\x4f\x06\xc8\x97\x99\x78\xa9\x05\
+        \x6c\x84\x83\xf4\xa1\xb6\x3d\x0d\xcc\xef\xe3\xa2\x8a\x2f\x32\x3e\
+        \x1c\xdc\xca\x40\xeb\xf0\x06\xac\x76\xef\x03\x97\x15\x23\x46\x83\
+        \x7b\x12\x77\xd3\xe7\xfa\xa9\xc9\x65\x3b\x19\x07\x50\x98\x52\x7b\
+    ";
+    test_kangaroo_twelve256("", pattern(17), 64, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_with_message3() {
+    let expected = b"\
+        \xb0\x62\x75\xd2\x84\xcd\x1c\xf2\x05\xbc\xbe\x57\xdc\xcd\x3e\xc1\
+        \xff\x66\x86\xe3\xed\x15\x77\x63\x83\xe1\xf2\xfa\x3c\x6a\xc8\xf0\
+        \x8b\xf8\xa1\x62\x82\x9d\xb1\xa4\x4b\x2a\x43\xff\x83\xdd\x89\xc3\
+        \xcf\x1c\xeb\x61\xed\xe6\x59\x76\x6d\x5c\xcf\x81\x7a\x62\xba\x8d\
+    ";
+    test_kangaroo_twelve256("", pattern(83521), 64, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_with_custom_string() {
+    let expected = b"\
+        \x92\x80\xf5\xcc\x39\xb5\x4a\x5a\x59\x4e\xc6\x3d\xe0\xbb\x99\x37\
+        \x1e\x46\x09\xd4\x4b\xf8\x45\xc2\xf5\xb8\xc3\x16\xd7\x2b\x15\x98\
+        \x11\xf7\x48\xf2\x3e\x3f\xab\xbe\x5c\x32\x26\xec\x96\xc6\x21\x86\
+        \xdf\x2d\x33\xe9\xdf\x74\xc5\x06\x9c\xee\xcb\xb4\xdd\x10\xef\xf6\
+    ";
+    test_kangaroo_twelve256(pattern(1), "", 64, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_with_custom_string_and_message() {
+    let expected = b"\
+        \x47\xef\x96\xdd\x61\x6f\x20\x09\x37\xaa\x78\x47\xe3\x4e\xc2\xfe\
+        \xae\x80\x87\xe3\x76\x1d\xc0\xf8\xc1\xa1\x54\xf5\x1d\xc9\xcc\xf8\
+        \x45\xd7\xad\xbc\xe5\x7f\xf6\x4b\x63\x97\x22\xc6\xa1\x67\x2e\x3b\
+        \xf5\x37\x2d\x87\xe0\x0a\xff\x89\xbe\x97\x24\x07\x56\x99\x88\x53\
+    ";
+    test_kangaroo_twelve256(pattern(41), [0xff], 64, expected);
+}
+
+#[test]
+fn kangaroo_twelve256_with_custom_string_and_message2() {
+    let expected = b"\
+        \xe0\x91\x1c\xc0\x00\x25\xe1\x54\x08\x31\xe2\x66\xd9\x4a\xdd\x9b\
+        \x98\x71\x21\x42\xb8\x0d\x26\x29\xe6\x43\xaa\xc4\xef\xaf\x5a\x3a\
+        \x30\xa8\x8c\xbf\x4a\xc2\xa9\x1a\x24\x32\x74\x30\x54\xfb\xcc\x98\
+        \x97\x67\x0e\x86\xba\x8c\xec\x2f\xc2\xac\xe9\xc9\x66\x36\x97\x24\
+    ";
+    test_kangaroo_twelve256(
+        pattern(68921),
+        [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        64,
+        expected,
+    );
+}
+
+#[test]
+fn kangaroo_twelve256_xof() {
+    let mut expected = [0u8; 128];
+    let mut kangaroo = KangarooTwelve256::new(b"");
+    kangaroo.update(&pattern(8193));
+    kangaroo.clone().finalize(&mut expected);
+
+    let mut output = [0u8; 128];
+    let mut xof = kangaroo.into_xof();
+    xof.squeeze(&mut output[..33]);
+    xof.squeeze(&mut output[33..]);
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+fn kangaroo_twelve_batched_leaves_match_incremental() {
+    // 13 chunks in one call are hashed as 8 + 4 leaves at once, the rest byte by byte
+    let input = pattern(13 * 8192 + 5);
+    let mut expected = [0u8; 32];
+    let mut output = [0u8; 32];
+
+    let mut kangaroo = KangarooTwelve::new(b"custom");
+    for chunk in input.chunks(1000) {
+        kangaroo.update(chunk);
+    }
+    kangaroo.finalize(&mut expected);
+
+    let mut kangaroo = KangarooTwelve::new(b"custom");
+    kangaroo.update(&input[..100]);
+    kangaroo.update(&input[100..]);
+    kangaroo.finalize(&mut output);
+    assert_eq!(expected, output);
+
+    let mut kangaroo = KangarooTwelve256::new(b"custom");
+    for chunk in input.chunks(1000) {
+        kangaroo.update(chunk);
+    }
+    let mut expected = [0u8; 64];
+    kangaroo.finalize(&mut expected);
+
+    let mut kangaroo = KangarooTwelve256::new(b"custom");
+    kangaroo.update(&input);
+    let mut output = [0u8; 64];
+    kangaroo.finalize(&mut output);
+    assert_eq!(expected, output);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn kangaroo_twelve_hash_parallel_matches_sequential() {
+    // the longest input spans more chunks than are hashed in one parallel window
+    for len in &[
+        0,
+        1,
+        8191,
+        8192,
+        8193,
+        3 * 8192 + 1,
+        21 * 8192 + 5,
+        8195 * 8192,
+    ] {
+        let input = pattern(*len);
+
+        let mut expected = [0u8; 32];
+        let mut kangaroo = KangarooTwelve::new(b"custom");
+        kangaroo.update(&input);
+        kangaroo.finalize(&mut expected);
+        let mut output = [0u8; 32];
+        KangarooTwelve::hash_parallel(&input, b"custom", &mut output);
+        assert_eq!(expected, output);
+
+        let mut expected = [0u8; 64];
+        let mut kangaroo = KangarooTwelve256::new(b"");
+        kangaroo.update(&input);
+        kangaroo.finalize(&mut expected);
+        let mut output = [0u8; 64];
+        KangarooTwelve256::hash_parallel(&input, b"", &mut output);
+        assert_eq!(&expected[..], &output[..]);
+    }
+}
@@ -28,3 +28,39 @@ fn string_keccak_256() {
     keccak.finalize(&mut in_and_out);
     assert_eq!(expected, &in_and_out);
 }
+
+#[test]
+fn keccak_batch_matches_one_by_one() {
+    let inputs: Vec<Vec<u8>> = (0..11).map(|i| vec![i as u8; 100 + i * 20]).collect();
+    let mut outputs = vec![[0u8; 32]; inputs.len()];
+    Keccak::v256_batch(&inputs, &mut outputs);
+    for (input, output) in inputs.iter().zip(&outputs) {
+        let mut expected = [0u8; 32];
+        let mut keccak = Keccak::v256();
+        keccak.update(input);
+        keccak.finalize(&mut expected);
+        assert_eq!(&expected, output);
+    }
+}
+
+#[test]
+fn keccak_steps_match_update_and_finalize() {
+    let input: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+    for &len in &[0, 135, 136, 137, 1000] {
+        let mut expected = [0u8; 32];
+        let mut keccak = Keccak::v256();
+        keccak.update(&input[..len]);
+        keccak.finalize(&mut expected);
+
+        for &rounds in &[1, 5, 24, 100] {
+            let mut output = [0u8; 32];
+            let mut keccak = Keccak::v256();
+            while keccak.update_step(&input[..len], rounds).is_pending() {}
+            let mut pending = keccak.finalize_step(&mut output, rounds);
+            while let Some(keccak) = pending {
+                pending = keccak.finalize_step(&mut output, rounds);
+            }
+            assert_eq!(expected, output);
+        }
+    }
+}
@@ -0,0 +1,666 @@
+use tiny_keccak::{
+    chi, iota, keccak_p, keccak_p_inverse, keccak_p_rounds, keccak_p_rounds_inverse,
+    keccak_p_traced, keccakf_times2, keccakf_times4, keccakf_times8, keccakp_times2,
+    keccakp_times4, keccakp_times8, pi, rho, theta, ResumableKeccak, Step,
+};
+
+#[test]
+fn keccak_p_24_rounds() {
+    let mut state = [0u64; 25];
+    let expected = [
+        0xf1258f7940e1dde7,
+        0x84d5ccf933c0478a,
+        0xd598261ea65aa9ee,
+        0xbd1547306f80494d,
+        0x8b284e056253d057,
+        0xff97a42d7f8e6fd4,
+        0x90fee5a0a44647c4,
+        0x8c5bda0cd6192e76,
+        0xad30a6f71b19059c,
+        0x30935ab7d08ffc64,
+        0xeb5aa93f2317d635,
+        0xa9a6e6260d712103,
+        0x81a57c16dbcf555f,
+        0x43b831cd0347c826,
+        0x01f22f1a11a5569f,
+        0x05e5635a21d9ae61,
+        0x64befef28cc970f2,
+        0x613670957bc46611,
+        0xb87c5a554fd00ecb,
+        0x8c3ee88a1ccf32c8,
+        0x940c7922ae3a2614,
+        0x1841f924a2c509e4,
+        0x16f53526e70465c2,
+        0x75f644e97f30a13b,
+        0xeaf1ff7b5ceca249,
+    ];
+
+    keccak_p(&mut state, 24);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccak_p_1_round() {
+    let mut state = [0u64; 25];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = i as u64;
+    }
+    let expected = [
+        0x8000180080008012,
+        0x0000e00001800000,
+        0x00001800000cc000,
+        0x000000000180001a,
+        0x0000e000000cc000,
+        0x00000001d0000080,
+        0x0003000002200000,
+        0x2000000000000083,
+        0x00030001d0000000,
+        0x2000000002200003,
+        0x0000000026000012,
+        0x0000000000003a00,
+        0x0000000026380000,
+        0x0000000000003812,
+        0x0000000000380200,
+        0x0000000178000c00,
+        0x000001f0000f0000,
+        0x0900000000000c00,
+        0x00000001780f0000,
+        0x090001f000000000,
+        0x4000128000000003,
+        0x0b00280000000000,
+        0x0000128000000074,
+        0x40002a0000000003,
+        0x0b00000000000074,
+    ];
+
+    keccak_p(&mut state, 1);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccak_p_12_rounds() {
+    let mut state = [0u64; 25];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = i as u64;
+    }
+    let expected = [
+        0x31ccb6fee8eeccfe,
+        0x57bf3dcca8d742e7,
+        0x33c23c8e00d5fd2d,
+        0x27408b85c213997d,
+        0x442b508505b591ae,
+        0xe7f3957f8698d9d0,
+        0x24e9ce4cb83dbdf3,
+        0xc6ed14e10f4998ba,
+        0xa445718c4dd30e41,
+        0xa618c4ddc4f4c14b,
+        0x862dab386c0b9ed0,
+        0x0fdade9dec4f977c,
+        0x38aa031a06ff1231,
+        0xf4b748a9ffecfc5c,
+        0xd0af5893c33a5f19,
+        0x4dc1ff1ef5fa9c46,
+        0xb15d80df5456c26b,
+        0x3a66709440a0c35b,
+        0xebbdd410f2e7a223,
+        0x7020a73b189a733d,
+        0xa3ea1df2b9a8f601,
+        0xd15d52bc81a76225,
+        0xeaac3058e82f6ac1,
+        0x1de0c38ae5544e5e,
+        0x72fa1a9d2dc565dd,
+    ];
+
+    keccak_p(&mut state, 12);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccak_p_rounds_matches_keccak_p() {
+    fn check<const ROUNDS: usize>() {
+        let mut a = [0x0123456789abcdefu64; 25];
+        let mut b = a;
+        keccak_p(&mut a, ROUNDS);
+        keccak_p_rounds::<ROUNDS>(&mut b);
+        assert_eq!(a, b);
+    }
+
+    check::<1>();
+    check::<6>();
+    check::<12>();
+    check::<14>();
+    check::<24>();
+}
+
+#[test]
+#[should_panic]
+fn keccak_p_zero_rounds() {
+    keccak_p(&mut [0u64; 25], 0);
+}
+
+#[test]
+#[should_panic]
+fn keccak_p_too_many_rounds() {
+    keccak_p(&mut [0u64; 25], 25);
+}
+
+fn check_times<const N: usize>(rounds: usize, permutation: fn(&mut [[u64; N]; 25])) {
+    let mut states = [[0u64; 25]; N];
+    let mut interleaved = [[0u64; N]; 25];
+    for (j, state) in states.iter_mut().enumerate() {
+        for (i, lane) in state.iter_mut().enumerate() {
+            *lane = (0x0123456789abcdef * (i as u64 + 1)).rotate_left(j as u32);
+            interleaved[i][j] = *lane;
+        }
+        keccak_p(state, rounds);
+    }
+
+    permutation(&mut interleaved);
+    for (j, state) in states.iter().enumerate() {
+        for (i, lane) in state.iter().enumerate() {
+            assert_eq!(*lane, interleaved[i][j]);
+        }
+    }
+}
+
+#[test]
+fn keccak_times_matches_keccak_p() {
+    check_times(24, keccakf_times2);
+    check_times(24, keccakf_times4);
+    check_times(24, keccakf_times8);
+    check_times(12, keccakp_times2);
+    check_times(12, keccakp_times4);
+    check_times(12, keccakp_times8);
+}
+
+#[cfg(feature = "lane_complementing")]
+#[test]
+fn keccakf_complementing_matches_keccak_p() {
+    let mut a = [0u64; 25];
+    for (i, lane) in a.iter_mut().enumerate() {
+        *lane = 0x0123456789abcdef_u64.rotate_left(5 * i as u32) ^ i as u64;
+    }
+    let mut b = a;
+
+    for _ in 0..3 {
+        keccak_p(&mut a, 24);
+        tiny_keccak::keccakf_complementing(&mut b);
+        assert_eq!(a, b);
+    }
+}
+
+/// Pseudo-random states from xorshift64, to check the inverses on many inputs.
+fn random_states(count: usize) -> impl Iterator<Item = [u64; 25]> {
+    let mut seed = 0x2545f4914f6cdd1du64;
+    (0..count).map(move |_| {
+        let mut state = [0u64; 25];
+        for lane in state.iter_mut() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            *lane = seed;
+        }
+        state
+    })
+}
+
+#[test]
+fn keccak_p_inverse_inverts_keccak_p() {
+    let states = random_states(16)
+        .chain(Some([0u64; 25]))
+        .chain(Some([!0u64; 25]));
+    for state in states {
+        for rounds in 1..=24 {
+            let mut a = state;
+            keccak_p(&mut a, rounds);
+            keccak_p_inverse(&mut a, rounds);
+            assert_eq!(state, a);
+
+            keccak_p_inverse(&mut a, rounds);
+            keccak_p(&mut a, rounds);
+            assert_eq!(state, a);
+        }
+    }
+}
+
+#[test]
+fn keccak_p_rounds_inverse_matches_keccak_p_inverse() {
+    fn check<const ROUNDS: usize>() {
+        for state in random_states(4) {
+            let mut a = state;
+            let mut b = state;
+            keccak_p_inverse(&mut a, ROUNDS);
+            keccak_p_rounds_inverse::<ROUNDS>(&mut b);
+            assert_eq!(a, b);
+        }
+    }
+
+    check::<1>();
+    check::<12>();
+    check::<24>();
+}
+
+#[test]
+#[should_panic]
+fn keccak_p_inverse_zero_rounds() {
+    keccak_p_inverse(&mut [0u64; 25], 0);
+}
+
+#[cfg(feature = "sha3")]
+#[test]
+fn keccakf_inverse_inverts_keccakf() {
+    for state in random_states(64) {
+        let mut a = state;
+        tiny_keccak::keccakf(&mut a);
+        tiny_keccak::keccakf_inverse(&mut a);
+        assert_eq!(state, a);
+    }
+}
+
+#[cfg(feature = "k12")]
+#[test]
+fn keccakp_inverse_inverts_keccakp() {
+    for state in random_states(64) {
+        let mut a = state;
+        tiny_keccak::keccakp(&mut a);
+        tiny_keccak::keccakp_inverse(&mut a);
+        assert_eq!(state, a);
+    }
+}
+
+#[test]
+fn keccak_p_traced_matches_keccak_p() {
+    for state in random_states(4) {
+        for rounds in &[1, 12, 24] {
+            let mut expected = state;
+            keccak_p(&mut expected, *rounds);
+
+            let mut records = 0;
+            let mut replayed = state;
+            let mut a = state;
+            keccak_p_traced(
+                &mut a,
+                *rounds,
+                &mut |round: usize, step: Step, traced: &[u64; 25]| {
+                    match step {
+                        Step::Theta => theta(&mut replayed),
+                        Step::Rho => rho(&mut replayed),
+                        Step::Pi => pi(&mut replayed),
+                        Step::Chi => chi(&mut replayed),
+                        Step::Iota => iota(&mut replayed, round),
+                    }
+                    assert_eq!(&replayed, traced);
+                    records += 1;
+                },
+            );
+            assert_eq!(expected, a);
+            assert_eq!(records, 5 * rounds);
+        }
+    }
+}
+
+#[test]
+fn keccak_p_traced_intermediate_values() {
+    // the start of the intermediate values of keccak-f[1600] on the all-zero state
+    let mut trace = Vec::new();
+    keccak_p_traced(
+        &mut [0u64; 25],
+        24,
+        &mut |round: usize, step: Step, state: &[u64; 25]| {
+            trace.push((round, step, *state));
+        },
+    );
+
+    let (round, step, state) = trace[4];
+    assert_eq!((round, step), (0, Step::Iota));
+    assert_eq!(state[0], 1);
+    assert!(state[1..].iter().all(|lane| *lane == 0));
+
+    // theta spreads the parity of the first column to the second and the last ones
+    let (round, step, state) = trace[5];
+    assert_eq!((round, step), (1, Step::Theta));
+    let mut expected = [0u64; 25];
+    expected[0] = 1;
+    for y in 0..5 {
+        expected[5 * y + 1] = 1;
+        expected[5 * y + 4] = 2;
+    }
+    assert_eq!(expected, state);
+    assert_eq!(format!("{}", step), "theta");
+}
+
+#[test]
+fn keccak_p_traced_second_permutation() {
+    // every step of the first two rounds of the second permutation in
+    // KeccakF-1600-IntermediateValues.txt, which starts from the output of the first one
+    let expected: [[[u64; 25]; 5]; 2] = [
+        [
+            // after theta of round 0
+            [
+                0xaf463273ca4d877d,
+                0xaf9fdf84cec209d0,
+                0x28c573db9cdda7ba,
+                0xabbcda349e794c02,
+                0xfd3cb094025a23b6,
+                0xa1f41927f522354e,
+                0xbbb4f6dd5944099e,
+                0x71068fc9ec9e2022,
+                0xbb993bf3eae000d3,
+                0x4687a426b0860f85,
+                0xb5391435a9bb8caf,
+                0x82ecf55bf0736f59,
+                0x7cf829d3e1485b0b,
+                0x5511acc9f2becd69,
+                0x77e6d18b71aca57e,
+                0x5b86de50ab75f4fb,
+                0x4ff4ed8f71cb3ea8,
+                0x9c6b255041436845,
+                0xaed5c751be290b84,
+                0xfa2a161b7cc6c129,
+                0xca6fc42824967c8e,
+                0x330bea595fc747be,
+                0xeba860e3dd836b96,
+                0x635fd9ed8ec9a474,
+                0x9ce501ea3ce551a8,
+            ],
+            // after rho of round 0
+            [
+                0xaf463273ca4d877d,
+                0x5f3fbf099d8413a1,
+                0x8a315cf6e73769ee,
+                0x49e794c02abbcda3,
+                0xa012d11db7e9e584,
+                0x522354ea1f41927f,
+                0x4099ebbb4f6dd594,
+                0x41a3f27b2788089c,
+                0x69ddcc9df9f57000,
+                0x426b0860f854687a,
+                0xa9c8a1ad4ddc657d,
+                0xb3d56fc1cdbd660b,
+                0x42d85be7c14e9f0a,
+                0x93e57d9ad2aa2359,
+                0xd652bf3bf368c5b8,
+                0xebe9f6b70dbca156,
+                0x67d509fe9db1ee39,
+                0x92a820a1b422ce35,
+                0xea37c5217095dab8,
+                0x2a161b7cc6c129fa,
+                0x10a09259f23b29bf,
+                0xcc2fa9657f1d1ef8,
+                0xdd750c1c7bb06d72,
+                0x74635fd9ed8ec9a4,
+                0x407a8f39546a2739,
+            ],
+            // after pi of round 0
+            [
+                0xaf463273ca4d877d,
+                0x4099ebbb4f6dd594,
+                0x42d85be7c14e9f0a,
+                0xea37c5217095dab8,
+                0x407a8f39546a2739,
+                0x49e794c02abbcda3,
+                0x426b0860f854687a,
+                0xa9c8a1ad4ddc657d,
+                0x67d509fe9db1ee39,
+                0xdd750c1c7bb06d72,
+                0x5f3fbf099d8413a1,
+                0x41a3f27b2788089c,
+                0x93e57d9ad2aa2359,
+                0x2a161b7cc6c129fa,
+                0x10a09259f23b29bf,
+                0xa012d11db7e9e584,
+                0x522354ea1f41927f,
+                0xb3d56fc1cdbd660b,
+                0x92a820a1b422ce35,
+                0x74635fd9ed8ec9a4,
+                0x8a315cf6e73769ee,
+                0x69ddcc9df9f57000,
+                0xd652bf3bf368c5b8,
+                0xebe9f6b70dbca156,
+                0xcc2fa9657f1d1ef8,
+            ],
+            // after chi of round 0
+            [
+                0xad0622374a4f8d77,
+                0xe8be6fbb7ffc9524,
+                0x429051ffc524ba0b,
+                0x4533f563fa905afc,
+                0x00e346b1514a77b9,
+                0xe067354d2f33c8a6,
+                0x047e00326875e27a,
+                0x31e8a5ad2fdc643f,
+                0x6757993e9dba6eb8,
+                0xdf7d043cabf44d2a,
+                0xcd7bb2894da630e0,
+                0x69b1f01f23c9003e,
+                0x8345fd9be290235c,
+                0x6509367ccb453bfa,
+                0x1020d22bd03321a3,
+                0x01c6fa1c77558184,
+                0x520b54ca2f431a4b,
+                0xd79630998431678b,
+                0x12b8a0a5a643ea35,
+                0x26425b3be58edbdf,
+                0x1c336fd4e53fec56,
+                0x40748c19f5615046,
+                0xd254b67b8169db10,
+                0xe9f9a2258d9ec050,
+                0xade3296c67dd0ef8,
+            ],
+            // after iota of round 0
+            [
+                0xad0622374a4f8d76,
+                0xe8be6fbb7ffc9524,
+                0x429051ffc524ba0b,
+                0x4533f563fa905afc,
+                0x00e346b1514a77b9,
+                0xe067354d2f33c8a6,
+                0x047e00326875e27a,
+                0x31e8a5ad2fdc643f,
+                0x6757993e9dba6eb8,
+                0xdf7d043cabf44d2a,
+                0xcd7bb2894da630e0,
+                0x69b1f01f23c9003e,
+                0x8345fd9be290235c,
+                0x6509367ccb453bfa,
+                0x1020d22bd03321a3,
+                0x01c6fa1c77558184,
+                0x520b54ca2f431a4b,
+                0xd79630998431678b,
+                0x12b8a0a5a643ea35,
+                0x26425b3be58edbdf,
+                0x1c336fd4e53fec56,
+                0x40748c19f5615046,
+                0xd254b67b8169db10,
+                0xe9f9a2258d9ec050,
+                0xade3296c67dd0ef8,
+            ],
+        ],
+        [
+            // after theta of round 1
+            [
+                0xc7054e4d3e5539ba,
+                0x9eae41d6df2c0e21,
+                0xadc6a7f92422ccd1,
+                0x38f3bfaba61d8721,
+                0x87117e67a39863a7,
+                0x8a6459375b297c6a,
+                0x726e2e5fc8a5797f,
+                0xdebe53abceda12e5,
+                0x1a97d3f6c137b365,
+                0x588f3cea59265934,
+                0xa778def339bc842c,
+                0x1fa1de7283199b3b,
+                0x6c130b9d03965586,
+                0x18c97cb497c8e627,
+                0x97d2eafd22e135bd,
+                0x6bc59666034f3548,
+                0x241b7aa78f93814e,
+                0x38c0c69f65371151,
+                0x6f78ea6dface37e8,
+                0xa1b063ed175ccfc1,
+                0x763003ae9125589a,
+                0x3664a27455b1cb43,
+                0x3d02407d606fadca,
+                0x9439e8edd1131d8d,
+                0x2a1111ba950f1ae6,
+            ],
+            // after rho of round 1
+            [
+                0xc7054e4d3e5539ba,
+                0x3d5c83adbe581c43,
+                0x6b71a9fe4908b334,
+                0xba61d872138f3bfa,
+                0x3d1cc31d3c388bf3,
+                0xb297c6a8a6459375,
+                0x5797f726e2e5fc8a,
+                0xaf94eaf3b684b977,
+                0xb28d4be9fb609bd9,
+                0xcea59265934588f3,
+                0x3bc6f799cde42165,
+                0x8779ca0c666cec7e,
+                0xb2ac3360985ce81c,
+                0x692f91cc4e3192f9,
+                0x709adecbe9757e91,
+                0x9e6a90d78b2ccc06,
+                0x7029c4836f54f1f2,
+                0x634fb29b88a89c60,
+                0x4dbf59c6fd0def1d,
+                0xb063ed175ccfc1a1,
+                0x0eba44956269d8c0,
+                0xd99289d156c72d0c,
+                0x47a0480fac0df5b9,
+                0x8d9439e8edd1131d,
+                0x446ea543c6b98a84,
+            ],
+            // after pi of round 1
+            [
+                0xc7054e4d3e5539ba,
+                0x5797f726e2e5fc8a,
+                0xb2ac3360985ce81c,
+                0x4dbf59c6fd0def1d,
+                0x446ea543c6b98a84,
+                0xba61d872138f3bfa,
+                0xcea59265934588f3,
+                0x3bc6f799cde42165,
+                0x7029c4836f54f1f2,
+                0x47a0480fac0df5b9,
+                0x3d5c83adbe581c43,
+                0xaf94eaf3b684b977,
+                0x692f91cc4e3192f9,
+                0xb063ed175ccfc1a1,
+                0x0eba44956269d8c0,
+                0x3d1cc31d3c388bf3,
+                0xb297c6a8a6459375,
+                0x8779ca0c666cec7e,
+                0x634fb29b88a89c60,
+                0x8d9439e8edd1131d,
+                0x6b71a9fe4908b334,
+                0xb28d4be9fb609bd9,
+                0x709adecbe9757e91,
+                0x9e6a90d78b2ccc06,
+                0xd99289d156c72d0c,
+            ],
+            // after chi of round 1
+            [
+                0x672d4e0d264d39ae,
+                0x1a84bfa087e4fb8b,
+                0xb2ec97619aece89c,
+                0xcebe13cac549de27,
+                0x54fc146106194e84,
+                0x8b23bdea5f2f1afe,
+                0x8e8c9267b1555861,
+                0x3c46ff954ded256c,
+                0xc86854f37cd6fbb0,
+                0x03244a0a2c4d75b8,
+                0x7d7792a1f6691ecb,
+                0x3fd486e0a64af877,
+                0x67b7914c6c118ab9,
+                0x81276e3fc0dfc5a2,
+                0x8c3a2cc762ed79f4,
+                0x3874cb197c10e7f9,
+                0xd291f63b2ec58375,
+                0x0be9c36c033def63,
+                0x5347708e98801482,
+                0x0f173d486f940319,
+                0x2b633dfc491dd734,
+                0x3ced4bfdf9681bdf,
+                0x310ad7cbbdb65f99,
+                0xbc0bb0f982245e36,
+                0x491ecbd0e4a725c5,
+            ],
+            // after iota of round 1
+            [
+                0x672d4e0d264db92c,
+                0x1a84bfa087e4fb8b,
+                0xb2ec97619aece89c,
+                0xcebe13cac549de27,
+                0x54fc146106194e84,
+                0x8b23bdea5f2f1afe,
+                0x8e8c9267b1555861,
+                0x3c46ff954ded256c,
+                0xc86854f37cd6fbb0,
+                0x03244a0a2c4d75b8,
+                0x7d7792a1f6691ecb,
+                0x3fd486e0a64af877,
+                0x67b7914c6c118ab9,
+                0x81276e3fc0dfc5a2,
+                0x8c3a2cc762ed79f4,
+                0x3874cb197c10e7f9,
+                0xd291f63b2ec58375,
+                0x0be9c36c033def63,
+                0x5347708e98801482,
+                0x0f173d486f940319,
+                0x2b633dfc491dd734,
+                0x3ced4bfdf9681bdf,
+                0x310ad7cbbdb65f99,
+                0xbc0bb0f982245e36,
+                0x491ecbd0e4a725c5,
+            ],
+        ],
+    ];
+    let steps = [Step::Theta, Step::Rho, Step::Pi, Step::Chi, Step::Iota];
+
+    let mut state = [0u64; 25];
+    keccak_p(&mut state, 24);
+    let mut trace = Vec::new();
+    keccak_p_traced(
+        &mut state,
+        24,
+        &mut |round: usize, step: Step, state: &[u64; 25]| {
+            trace.push((round, step, *state));
+        },
+    );
+
+    for (round, expected) in expected.iter().enumerate() {
+        for (step, expected) in steps.iter().zip(expected.iter()) {
+            assert_eq!(trace[5 * round + *step as usize], (round, *step, *expected));
+        }
+    }
+}
+
+#[test]
+fn resumable_keccak_matches_keccak_p() {
+    for state in random_states(2) {
+        for rounds in &[1, 12, 24] {
+            let mut expected = state;
+            keccak_p(&mut expected, *rounds);
+            keccak_p(&mut expected, *rounds);
+
+            for budget in &[1, 5, 24] {
+                let mut a = state;
+                let mut permutation = ResumableKeccak::keccak_p(*rounds);
+                // the permutation starts over once it is ready
+                for _ in 0..2 {
+                    let mut calls = 0;
+                    while permutation.resume(&mut a, *budget).is_pending() {
+                        calls += 1;
+                        assert_eq!(permutation.remaining(), rounds - calls * budget);
+                    }
+                    assert_eq!(calls + 1, rounds.div_ceil(*budget));
+                }
+                assert_eq!(expected, a);
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use tiny_keccak::{keccakf200, keccakf400, keccakf800};
+
+#[test]
+fn keccakf200_zero_state() {
+    let mut state = [0u8; 25];
+    let expected = [
+        0x3c, 0x28, 0x26, 0x84, 0x1c, 0xb3, 0x5c, 0x17, 0x1e, 0xaa, 0xe9, 0xb8, 0x11, 0x13, 0x4c,
+        0xea, 0xa3, 0x85, 0x2c, 0x69, 0xd2, 0xc5, 0xab, 0xaf, 0xea,
+    ];
+
+    keccakf200(&mut state);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccakf200_state() {
+    let mut state = [0u8; 25];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = i as u8;
+    }
+    let expected = [
+        0x7f, 0x03, 0x40, 0xbd, 0x5e, 0xf9, 0xa9, 0xce, 0x6c, 0x77, 0xd1, 0x41, 0xea, 0x91, 0x23,
+        0x77, 0x2d, 0x83, 0xf0, 0x40, 0xbf, 0x23, 0x1c, 0xa5, 0x1c,
+    ];
+
+    keccakf200(&mut state);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccakf400_zero_state() {
+    let mut state = [0u16; 25];
+    let expected = [
+        0x09f5, 0x40ac, 0x0fa9, 0x14f5, 0xe89f, 0xeca0, 0x5bd1, 0x7870, 0xeff0, 0xbf8f, 0x0337,
+        0x6052, 0xdc75, 0x0ec9, 0xe776, 0x5246, 0x59a1, 0x5d81, 0x6d95, 0x6e14, 0x633e, 0x58ee,
+        0x71ff, 0x714c, 0xb38e,
+    ];
+
+    keccakf400(&mut state);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccakf400_state() {
+    let mut state = [0u16; 25];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = i as u16;
+    }
+    let expected = [
+        0x5989, 0x6d1d, 0x4452, 0xe07d, 0xdba9, 0xc4f1, 0x2334, 0xd476, 0xa82c, 0xd366, 0xd18a,
+        0xf20b, 0xb720, 0xcdd2, 0x33f5, 0x3989, 0xe136, 0x282f, 0x3cfc, 0x1088, 0x3bca, 0xdb31,
+        0x9360, 0x4cdf, 0x1d0f,
+    ];
+
+    keccakf400(&mut state);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccakf800_zero_state() {
+    let mut state = [0u32; 25];
+    let expected = [
+        0xe531d45d, 0xf404c6fb, 0x23a0bf99, 0xf1f8452f, 0x51ffd042, 0xe539f578, 0xf00b80a7,
+        0xaf973664, 0xbf5af34c, 0x227a2424, 0x88172715, 0x9f685884, 0xb15cd054, 0x1bf4fc0e,
+        0x6166fa91, 0x1a9e599a, 0xa3970a1f, 0xab659687, 0xafab8d68, 0xe74b1015, 0x34001a98,
+        0x4119eff3, 0x930a0e76, 0x87b28070, 0x11efe996,
+    ];
+
+    keccakf800(&mut state);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccakf800_state() {
+    let mut state = [0u32; 25];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = i as u32;
+    }
+    let expected = [
+        0xe42f30cf, 0x1ecfd968, 0x0d89e1f7, 0x79b7d904, 0xdff14170, 0x30f6c89d, 0x64a7cfd3,
+        0x2d78ad80, 0xabc3edf5, 0x0086ed7c, 0xff6f46ea, 0xe0fd3d17, 0x71ca0a08, 0x4d284667,
+        0xb3869f22, 0xce40f3f9, 0x081e2727, 0xc6690c35, 0x77a234e8, 0x5bbac530, 0x2963ce54,
+        0x712973a9, 0x0a290a4f, 0xfd459217, 0xb6ccb003,
+    ];
+
+    keccakf800(&mut state);
+    assert_eq!(expected, state);
+}
@@ -331,3 +331,23 @@ fn test_kmac256_xof_three() {
     xof.squeeze(&mut output);
     assert_eq!(expected as &[u8], &output as &[u8]);
 }
+
+#[cfg(feature = "fault_detection")]
+#[test]
+fn test_kmac_try_finalize_matches_finalize() {
+    let key = b"\
+        \x40\x41\x42\x43\x44\x45\x46\x47\x48\x49\x4A\x4B\x4C\x4D\x4E\x4F\
+        \x50\x51\x52\x53\x54\x55\x56\x57\x58\x59\x5A\x5B\x5C\x5D\x5E\x5F\
+    ";
+    let data = b"\x00\x01\x02\x03";
+    let custom = b"My Tagged Application";
+    let expected = b"\
+        \x3B\x1F\xBA\x96\x3C\xD8\xB0\xB5\x9E\x8C\x1A\x6D\x71\x88\x8B\x71\
+        \x43\x65\x1A\xF8\xBA\x0A\x70\x70\xC0\x97\x9E\x28\x11\x32\x4A\xA5\
+    ";
+    let mut output = [0u8; 32];
+    let mut kmac = Kmac::v128(key, custom);
+    kmac.update(data);
+    assert_eq!(kmac.try_finalize(&mut output), Ok(()));
+    assert_eq!(expected, &output);
+}
@@ -0,0 +1,412 @@
+// All expected values are the output of the reference implementation of Kravatte, Kravatte-SANE
+// and Kravatte-SANSE in the eXtended Keccak Code Package (XKCP).
+
+use tiny_keccak::{InvalidTag, Kravatte, KravatteSane, KravatteSanse};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn key() -> Vec<u8> {
+    (0..16).collect()
+}
+
+fn kravatte(strings: &[&[u8]], offset: usize, expected: &[u8]) {
+    let mut kravatte = Kravatte::new(&key());
+    for string in strings {
+        kravatte.update(string);
+        kravatte.end_string();
+    }
+    let mut output = vec![0u8; expected.len()];
+    kravatte.expand(offset, &mut output);
+    assert_eq!(expected, &output[..]);
+}
+
+#[test]
+fn kravatte_empty() {
+    let expected = b"\
+        \x65\xc8\xa0\x2a\xa1\x09\xca\xff\x2a\x84\x6a\x46\xd6\x34\x6f\xf6\
+        \x2f\xe0\xe4\x13\x58\xc8\xad\x89\xf2\x4a\x2f\x1d\xf9\x99\xba\x73\
+        \x94\xef\x50\xe5\x8b\xfa\x7c\x96\x8a\xd8\x57\x58\x12\x40\x0f\x76\
+        \x82\xda\x1e\x99\x37\x72\xff\x44\x38\x1b\x23\x12\x54\xdb\x22\xed\
+    ";
+    kravatte(&[b""], 0, expected);
+}
+
+#[test]
+fn kravatte_one_block() {
+    let expected = b"\
+        \x85\x4e\xe8\x2f\xea\x79\xc8\x74\xaa\x39\xd6\xe5\xc9\xe2\x20\xcb\
+        \xfc\x7e\xfa\xef\x6f\x92\x40\x97\xfd\xa8\xbb\xf1\x08\x94\x8f\xd3\
+    ";
+    kravatte(&[&pattern(199)], 0, expected);
+}
+
+#[test]
+fn kravatte_two_blocks() {
+    let expected = b"\
+        \x54\xa4\xf4\x15\xde\x5e\x9a\xa7\x1c\xe5\x73\xe9\x1c\x94\x6c\x76\
+        \xbb\xdd\xae\x6e\xf1\x5d\x9a\x4d\x0c\x0e\x50\x08\x18\x05\x89\x41\
+    ";
+    kravatte(&[&pattern(200)], 0, expected);
+}
+
+#[test]
+fn kravatte_long() {
+    let expected = b"\
+        \x2e\x76\x2c\xf1\x98\xf4\x1b\x77\xf7\x8e\xb7\x20\x42\x41\xdb\x9b\
+        \x15\x9f\xa3\x89\x7e\xdc\x4e\x4c\x30\x45\x5e\x8d\xe5\xbe\x71\xa6\
+        \xbc\xe8\x52\x46\x90\x18\x16\xd1\x46\x5f\x68\x33\x44\xc6\xb1\xec\
+        \xcf\x81\x8f\x87\x2a\x29\x97\xab\x49\xa3\x12\xc3\x92\x96\x36\xf3\
+        \x35\x7c\x98\x3c\x4f\xe3\x23\xc5\xfc\xdf\x4f\x1b\xe8\xe9\xe7\x12\
+        \x87\xb1\x93\x0b\x7e\xcd\x3e\x22\x1b\xab\x6a\x02\x13\x21\xc0\x95\
+        \x83\x4f\x39\xdb\x83\xd1\x09\xe9\x76\x29\x6a\x2a\x2e\x32\x9c\x85\
+        \x36\x32\x37\x27\xca\x32\xa9\x83\xaa\x6b\x7a\xa9\x08\x1b\x4e\x84\
+        \x31\x40\x26\x0f\xdd\x04\x1e\x8a\xe8\xae\x3a\xa3\x81\x74\xdb\x01\
+        \xf6\xfb\xd6\x16\x52\x2d\xaf\x04\x5e\x67\x0d\x25\x7a\xef\x5c\x45\
+        \x39\xdc\x91\x1f\x49\x73\x08\x8d\x25\x90\x09\x39\x30\xb4\x6a\x10\
+        \xfe\x95\x1b\x42\x15\xf5\x78\x70\x42\x8a\x96\x40\xcd\x91\x75\x9e\
+        \x7e\x57\x82\xcc\x1d\xcf\x9c\xe4\xf0\x82\x4f\xa9\x24\xf1\xa1\x48\
+        \x83\xb0\xb6\xcf\x04\xfd\x29\x6f\x1c\x78\xea\xd7\x40\xfd\x1f\xf2\
+        \x80\x6a\x3f\x18\x15\x3d\x61\xd7\x79\x11\x48\xbc\x7e\xdb\x74\xb4\
+        \x37\x71\x77\x9d\xfe\x91\x65\xfd\xf5\x20\x80\x08\xf8\xef\x32\x3a\
+        \x6f\x80\xea\xaa\xe1\x37\x7d\x96\x6a\x97\xf1\xb8\x66\xfc\xf9\xcb\
+        \x0d\xdc\x33\x9e\x31\x47\x9e\xcb\x20\xfa\xb0\x8a\x3d\xb8\xd9\x30\
+        \x5d\xb7\xe8\x24\x96\x71\x01\x18\xd4\xd6\xc9\xc0\x1b\xc3\x96\xbd\
+        \x3f\x64\xa0\x4e\xb7\x47\xeb\x20\x3e\x6a\x13\xed\x06\xce\x6b\x41\
+        \x46\xbb\x7e\x6f\xef\x6f\x87\xac\xb5\xbf\x38\x58\x0c\xfc\x0d\xcf\
+        \x6a\x02\xd8\x69\x92\x06\xda\x5d\xd1\xbe\xf6\x04\xf5\xd7\xcc\x15\
+        \x79\x02\x19\x47\xbc\xc2\x55\x63\x31\x69\x99\x81\xa2\xaf\x2a\xd0\
+        \x14\x94\x36\x9d\xcf\x0c\xf1\xd5\xad\x44\x69\x34\x75\x8b\x9d\x9d\
+        \xed\x55\x4e\x5a\xee\xf0\xd6\xa2\x99\x71\xef\x34\x89\x8d\x14\xd1\
+    ";
+    kravatte(&[&pattern(1000)], 0, expected);
+}
+
+#[test]
+fn kravatte_sequence_offset() {
+    let expected = b"\
+        \xde\x7f\xb3\x7c\x30\x87\x11\x80\xd7\xeb\xaa\x7f\x66\x7c\xd8\xc8\
+        \x0e\x3b\xee\x6f\x72\x60\x2a\xd6\x93\x2b\x09\x71\xbd\x4a\xeb\xd1\
+        \x0d\xfd\x88\xe4\xf9\x85\xe2\xfe\x72\x76\xaa\xc8\xef\xd6\x9f\x38\
+        \x31\x1b\x19\x86\x26\x0c\x81\xb4\x74\x52\x8a\x05\xc0\x01\x79\x7e\
+        \x90\x28\x54\x77\xc0\x49\x28\x54\x2b\x75\x42\x7b\x5d\x3d\x08\xc9\
+        \x8c\x90\x5a\x34\xeb\xff\x77\x91\x96\xa0\xd9\xb1\x14\x8c\x48\xfc\
+        \x4e\x5d\x4e\xf6\
+    ";
+    kravatte(&[&pattern(3), b"", &pattern(300)], 350, expected);
+}
+
+#[test]
+fn kravatte_incremental() {
+    let mut kravatte = Kravatte::new(&key());
+    let input = pattern(1000);
+    let mut expected = [0u8; 32];
+    let mut output = [0u8; 32];
+
+    for chunk in input.chunks(97) {
+        kravatte.update(chunk);
+    }
+    kravatte.end_string();
+    kravatte.expand(0, &mut expected);
+    kravatte.expand(0, &mut output);
+    assert_eq!(expected, output);
+
+    kravatte.update(b"more");
+    kravatte.end_string();
+    kravatte.expand(0, &mut output);
+    assert_ne!(expected, output);
+}
+
+#[test]
+fn kravatte_sane_session() {
+    let nonce: Vec<u8> = (100..116).collect();
+    let mut tag = [0u8; 16];
+    let expected_tag = b"\x3b\xa7\x52\xc2\x7a\xc3\x83\x28\xb7\xab\xb2\x38\xc1\x79\xa4\x04";
+    let mut sane = KravatteSane::new(&key(), &nonce, &mut tag);
+    assert_eq!(expected_tag, &tag);
+
+    let mut data = pattern(200);
+    let expected_ciphertext = b"\
+        \x2a\x12\xa6\xc8\x32\xe8\x9e\xa3\x23\xe0\xcb\x20\xd1\xea\x3e\xf8\
+        \x9c\x47\xda\xe7\xfb\xc3\xf5\xda\x04\x0e\x78\xe5\xd4\x4f\x26\xa0\
+        \x73\x84\x73\x7c\xa8\xc9\xa7\xe2\xa2\xc0\xf7\xc8\x74\x67\xe8\x9e\
+        \x49\xbf\x30\x0c\x72\x0f\xca\x92\x40\x8e\xad\x41\xb1\xdd\x9e\xf5\
+        \x13\x7e\xa3\xaf\x17\x8e\x1d\x49\x04\x59\x77\x23\x40\x40\x49\x2f\
+        \xbc\x3a\x3f\x2b\xcf\xb0\x9d\xb3\xfc\x6b\x13\xc9\xab\xe7\x78\xc8\
+        \xfe\x0d\x31\x0c\xc2\xef\x87\x12\x6f\x32\x9e\x52\xe5\x40\xd6\xc1\
+        \x4b\x98\xe7\xba\x4a\x58\x7a\x8e\x84\x8d\x8b\x14\x76\x89\x8d\xd7\
+        \x64\xb6\xa5\x3f\xc5\x1c\x7a\xbe\xa7\xfc\xf1\x61\x62\xad\xde\xb0\
+        \x8c\xa9\x03\x55\x6f\xa8\x53\xd3\x49\xd2\x68\xff\x78\x91\xf4\x16\
+        \x90\xad\x07\xc3\xf6\xe4\xcf\xee\xd1\x23\x62\x68\xa9\xf4\x5f\x86\
+        \x7a\xef\xbb\xfb\x29\x61\x3a\x1c\x9a\xdb\x86\xbc\x4f\xb5\x9e\xbb\
+        \x62\xe0\x1d\xb8\xa4\x22\x79\xc3\
+    ";
+    let expected_tag = b"\xda\xa9\xfe\xd4\x6e\xc8\x2e\x0a\x46\x40\x43\x20\x00\xa3\xd7\x67";
+    sane.wrap(b"header", &mut data, &mut tag);
+    assert_eq!(&expected_ciphertext[..], &data[..]);
+    assert_eq!(expected_tag, &tag);
+
+    let expected_tag = b"\x0f\x5e\xd8\x8e\x03\x3a\xbb\xbf\x2a\x5a\x01\x85\xd2\x00\xac\x32";
+    sane.wrap(b"", &mut [], &mut tag);
+    assert_eq!(expected_tag, &tag);
+
+    let mut data = pattern(3);
+    let expected_ciphertext = b"\x60\x4e\x57";
+    let expected_tag = b"\x59\x1d\x3c\x19\x1c\xa8\xba\xc4\x1b\x78\xc0\x88\xb3\x10\xfa\x03";
+    sane.wrap(&pattern(400), &mut data, &mut tag);
+    assert_eq!(expected_ciphertext, &data[..]);
+    assert_eq!(expected_tag, &tag);
+}
+
+#[test]
+fn kravatte_sane_session_unwrap() {
+    let mut tag = [0u8; 16];
+    let mut sender = KravatteSane::new(&key(), b"nonce", &mut tag);
+    let mut receiver = KravatteSane::new(&key(), b"nonce", &mut tag);
+
+    for (ad_len, len) in &[(0, 0), (0, 1), (2, 199), (200, 200), (201, 0), (1000, 500)] {
+        let ad = pattern(*ad_len);
+        let plaintext = pattern(*len);
+        let mut data = plaintext.clone();
+        sender.wrap(&ad, &mut data, &mut tag);
+        receiver.unwrap(&ad, &mut data, &tag).unwrap();
+        assert_eq!(plaintext, data);
+    }
+}
+
+#[test]
+fn kravatte_sane_unwrap_invalid() {
+    let mut tag = [0u8; 16];
+    let sane = KravatteSane::new(&key(), b"nonce", &mut tag);
+    let mut ciphertext = pattern(300);
+    sane.clone().wrap(b"ad", &mut ciphertext, &mut tag);
+
+    let mut data = ciphertext.clone();
+    let result = sane.clone().unwrap(b"da", &mut data, &tag);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(data, vec![0u8; 300]);
+
+    let mut data = ciphertext.clone();
+    data[10] ^= 1;
+    let result = sane.clone().unwrap(b"ad", &mut data, &tag);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(data, vec![0u8; 300]);
+
+    let mut data = ciphertext.clone();
+    tag[0] ^= 1;
+    let result = sane.clone().unwrap(b"ad", &mut data, &tag);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(data, vec![0u8; 300]);
+}
+
+#[test]
+fn kravatte_sanse_session() {
+    let mut sanse = KravatteSanse::new(&key());
+
+    let mut data = pattern(200);
+    let mut tag = [0u8; 32];
+    let expected_ciphertext = b"\
+        \xb7\x45\xff\x8b\xd5\xcc\x0b\x1b\xd1\x15\x7d\x24\x56\x60\xe5\x7f\
+        \x2b\x21\xf5\x37\xa8\xc5\xf6\x40\x45\x71\xc3\x80\x67\x25\x91\x30\
+        \xfb\x26\x2d\xf0\x2f\xdb\x78\xc4\x2d\x9c\x6c\x24\x26\x0f\x94\x4e\
+        \xe2\x91\x9d\x59\x4f\xf2\xcc\x94\x22\xb9\x79\x86\x17\xe8\xcc\x3e\
+        \xd9\x7a\xef\xef\x98\xa0\xfd\x11\x27\xe9\x8b\xb1\xff\xa0\x25\x6f\
+        \x59\x30\xff\xcc\x4a\x8e\x72\x83\x80\xa0\x47\x2f\x89\x98\x60\xe9\
+        \x65\x19\x0b\x90\x31\x01\x4e\x7d\xf4\xb7\x60\x8b\x48\x81\x90\xb3\
+        \x48\x2d\xc8\xd6\xdb\xb9\xe3\xde\x6c\x26\x14\xff\x9a\x31\x23\xf6\
+        \x9c\x23\xa4\x0e\x99\x9d\x71\x0e\x36\x7b\x18\xc0\x3d\x45\xbb\x65\
+        \xff\x1b\xe4\xd4\xdd\x7f\x6e\x45\x21\x96\x3f\x89\x2f\xdf\x51\xdb\
+        \x83\x0d\x8a\x73\xc1\x51\xba\xf2\x8f\xf8\x81\xe8\x91\xd2\xf1\xb3\
+        \x94\x63\x19\x32\x18\x4e\x7e\xea\xb1\x3e\xae\x0d\x47\x96\x9d\x01\
+        \x69\x5c\x1c\xe6\x2c\xec\xaa\xfb\
+    ";
+    let expected_tag = b"\
+        \xb3\xd1\x71\x23\xb2\x48\xec\xa9\x7f\x99\x16\xed\x1a\xca\xf6\xae\
+        \xa8\x19\x28\x64\xf1\x8f\xaf\x13\x15\xbd\x53\x41\x4e\x33\xc7\x11\
+    ";
+    sanse.wrap(b"header", &mut data, &mut tag);
+    assert_eq!(&expected_ciphertext[..], &data[..]);
+    assert_eq!(expected_tag, &tag);
+
+    let expected_tag = b"\
+        \x64\xb6\x27\xb2\x41\x22\xc1\x06\x50\x5b\xbe\x00\xee\x23\x76\x3b\
+        \x75\x1c\x44\x50\x3e\x05\x91\xd0\x25\x98\x3a\x9b\x74\xdf\xc0\xe9\
+    ";
+    sanse.wrap(b"", &mut [], &mut tag);
+    assert_eq!(expected_tag, &tag);
+
+    let mut data = pattern(3);
+    let expected_ciphertext = b"\x1f\x6b\xc1";
+    let expected_tag = b"\
+        \xac\x79\x65\x15\xc8\xf5\xfb\x6d\xb7\xc9\x0b\x7f\x35\xda\xa6\xc5\
+        \xec\xb6\x0c\x3a\x4b\xd3\x12\x3d\xde\x7e\x9a\x2c\xc8\x60\xbd\x45\
+    ";
+    sanse.wrap(&pattern(400), &mut data, &mut tag);
+    assert_eq!(expected_ciphertext, &data[..]);
+    assert_eq!(expected_tag, &tag);
+}
+
+#[test]
+fn kravatte_sanse_session_unwrap() {
+    let mut sender = KravatteSanse::new(&key());
+    let mut receiver = KravatteSanse::new(&key());
+
+    for (ad_len, len) in &[(0, 0), (0, 1), (2, 199), (200, 200), (201, 0), (1000, 500)] {
+        let ad = pattern(*ad_len);
+        let plaintext = pattern(*len);
+        let mut data = plaintext.clone();
+        let mut tag = [0u8; 32];
+        sender.wrap(&ad, &mut data, &mut tag);
+        receiver.unwrap(&ad, &mut data, &tag).unwrap();
+        assert_eq!(plaintext, data);
+    }
+}
+
+#[test]
+fn kravatte_sanse_unwrap_invalid() {
+    let sanse = KravatteSanse::new(&key());
+    let mut ciphertext = pattern(300);
+    let mut tag = [0u8; 32];
+    sanse.clone().wrap(b"ad", &mut ciphertext, &mut tag);
+
+    let mut data = ciphertext.clone();
+    let result = sanse.clone().unwrap(b"da", &mut data, &tag);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(data, vec![0u8; 300]);
+
+    let mut data = ciphertext.clone();
+    data[10] ^= 1;
+    let result = sanse.clone().unwrap(b"ad", &mut data, &tag);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(data, vec![0u8; 300]);
+
+    let mut data = ciphertext.clone();
+    tag[0] ^= 1;
+    let result = sanse.clone().unwrap(b"ad", &mut data, &tag);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(data, vec![0u8; 300]);
+}
+
+fn xkcp_key() -> Vec<u8> {
+    pattern(32)
+}
+
+const XKCP_CASES: [(usize, usize); 6] = [(0, 1), (5, 0), (0, 0), (250, 199), (1, 401), (0, 200)];
+
+#[test]
+fn kravatte_xkcp_two_strings() {
+    let expected = b"\
+        \x09\x80\xd7\x90\x98\x0d\xf6\x03\xd2\xdd\x56\x7e\xf3\x68\xd0\x87\
+        \xcb\x46\x8c\x79\xf7\xda\xd3\x06\x65\xc0\x0d\x78\x85\xf7\xa4\x88\
+        \xa8\x1b\x89\xe6\x41\x0b\xc6\x79\x82\x84\xcd\x3e\x81\xa7\x75\x73\
+        \x1b\x23\xb2\x48\x10\x35\xeb\x2a\xb1\x81\x2b\x2f\xa3\x81\xf6\xb2\
+    ";
+    let mut kravatte = Kravatte::new(&xkcp_key());
+    let input = pattern(500);
+    kravatte.update(&input[..200]);
+    kravatte.end_string();
+    kravatte.update(&input);
+    kravatte.end_string();
+    let mut output = [0u8; 64];
+    kravatte.expand(600, &mut output);
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+fn kravatte_sane_xkcp_session() {
+    let expected_tag = b"\x63\x0c\xcd\x5e\x1c\xa1\xa6\x82\x2b\xe7\xbe\x07\x67\x45\xb4\x20";
+    let expected: [(&[u8], &[u8; 16]); 6] = [
+        (
+            b"\x84",
+            b"\x9d\xbb\x3a\x1b\x8d\xbf\xe2\x69\x17\xb9\x6b\x04\x12\x7d\xbb\x12",
+        ),
+        (
+            b"",
+            b"\xff\x2a\xda\xe2\xfd\xfc\x25\xd2\xd2\xb5\xbf\x26\x9a\xd3\x35\xaf",
+        ),
+        (
+            b"",
+            b"\xa9\x59\x6f\x88\x65\xaa\x07\xf5\xf5\x56\x37\x58\xa9\x89\x4e\xa3",
+        ),
+        (
+            b"\xa5\xef\xb0\x7f\x17\x96\x77\xe9\x78\xb3\x03\x55\xde\x23\xb1\x15",
+            b"\x42\x47\x3f\x61\xdc\x29\x76\x71\x40\xd6\x5e\x37\xda\xb1\xbe\x0f",
+        ),
+        (
+            b"\xd3\xfc\x27\x53\x0c\x2f\x02\xdb\x88\x67\xe7\x85\x0f\xd9\x12\xbf",
+            b"\x23\x79\x2a\x76\xa7\xdb\x89\x65\xf3\xa7\xad\xa5\x4a\xa3\xb3\xdb",
+        ),
+        (
+            b"\x78\xcc\x6e\x54\xfc\x77\xdc\x38\xf6\x50\xc9\xd4\xda\x9f\x0f\x38",
+            b"\x93\xa8\x30\x1b\x98\xb0\x52\xce\x4f\xe8\x72\xaf\x1a\x6a\xe7\x31",
+        ),
+    ];
+
+    let mut tag = [0u8; 16];
+    let mut sane = KravatteSane::new(&xkcp_key(), b"nonce", &mut tag);
+    assert_eq!(expected_tag, &tag);
+    for ((ad_len, len), (ciphertext, expected_tag)) in XKCP_CASES.iter().zip(expected.iter()) {
+        let mut data = pattern(*len);
+        sane.wrap(&pattern(*ad_len), &mut data, &mut tag);
+        assert_eq!(*ciphertext, &data[..ciphertext.len()]);
+        assert_eq!(*expected_tag, &tag);
+    }
+}
+
+#[test]
+fn kravatte_sanse_xkcp_session() {
+    let expected: [(&[u8], &[u8; 32]); 6] = [
+        (
+            b"\xc1",
+            b"\
+                \x84\xac\x8d\x8d\x4a\xf6\x4f\xb9\x40\xc5\x98\xa9\xcb\xed\xc1\x94\
+                \x67\xf4\xca\x04\x41\x36\xee\xe2\xee\x63\x5a\x54\x5d\x75\x90\xca\
+            ",
+        ),
+        (
+            b"",
+            b"\
+                \x52\x78\x37\xfb\x78\xf8\x92\x73\xa2\x47\x75\x27\x3b\x70\x32\xdb\
+                \x38\x4f\xfd\xe1\xe8\x0e\x59\x99\x43\xae\xe3\xfe\xc4\x43\x1f\xb1\
+            ",
+        ),
+        (
+            b"",
+            b"\
+                \x9d\xc1\xc0\xa7\x26\xc5\xed\xbc\xa7\x12\x70\x7f\xd5\x3c\x33\x20\
+                \x64\x7e\xb1\xc1\xec\xe0\x60\x5d\x37\x8d\x12\xa7\xe1\x64\x0d\x55\
+            ",
+        ),
+        (
+            b"\x2a\xb0\x43\xb3\x97\x30\x39\xef\xa7\xbc\x23\xa8\x14\xe7\x44\x64",
+            b"\
+                \xc3\xf5\x84\x70\x29\xfe\x0d\xb8\xcb\x94\x0c\xb8\xe9\xa9\xff\xe2\
+                \x27\x2c\xfd\x1b\x8b\x56\x70\x6c\xe0\x37\x9c\x4b\x00\xf8\xc8\x0e\
+            ",
+        ),
+        (
+            b"\x38\x4f\x04\x43\x07\x25\x6a\x8f\x19\x89\xda\xa8\xaf\x43\x63\x5c",
+            b"\
+                \xd6\x0e\x74\xd5\xb2\xe4\xd2\x5a\x32\xf9\x1a\x3d\x0f\xad\x98\x94\
+                \xd8\x44\xa1\x81\x7d\x8d\x6a\xf9\xb7\x95\x1f\x83\xdb\x00\xe1\xb1\
+            ",
+        ),
+        (
+            b"\x62\xc4\x01\x1f\x1a\xf0\xfb\x83\xdc\x78\x4d\x64\x34\xc0\x06\x4f",
+            b"\
+                \x3d\xfd\xd5\x2f\x5b\xc3\x08\xdc\xd8\x27\x42\x42\x13\x95\x2c\xb4\
+                \x17\x64\x02\x46\x00\xf6\xa6\xd1\x5e\x73\x6a\x4f\x35\x09\x4c\xe0\
+            ",
+        ),
+    ];
+
+    let mut tag = [0u8; 32];
+    let mut sanse = KravatteSanse::new(&xkcp_key());
+    for ((ad_len, len), (ciphertext, expected_tag)) in XKCP_CASES.iter().zip(expected.iter()) {
+        let mut data = pattern(*len);
+        sanse.wrap(&pattern(*ad_len), &mut data, &mut tag);
+        assert_eq!(*ciphertext, &data[..ciphertext.len()]);
+        assert_eq!(*expected_tag, &tag);
+    }
+}
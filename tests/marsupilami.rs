@@ -0,0 +1,125 @@
+use tiny_keccak::{Hasher, IntoXof, MarsupilamiFourteen, Xof};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn test_marsupilami_fourteen<A: AsRef<[u8]>, B: AsRef<[u8]>>(
+    custom_string: A,
+    message: B,
+    output_len: usize,
+    expected: &[u8],
+) {
+    let mut marsupilami = MarsupilamiFourteen::new(custom_string.as_ref());
+    marsupilami.update(message.as_ref());
+    let mut res = vec![0; output_len];
+    marsupilami.finalize(&mut res);
+    assert_eq!(&res[output_len - expected.len()..], expected);
+}
+
+#[test]
+fn empty_marsupilami_fourteen() {
+    let expected = b"\
+        \x6f\x66\xef\x14\x74\xeb\x53\x80\x7a\xa3\x29\x25\x7c\x76\x8b\xb8\
+        \x88\x93\xd9\xf0\x86\xe5\x1d\xa2\xf5\xc8\x0d\x17\xca\x0f\xc5\x7d\
+        \x5a\x24\xfa\xc8\x79\x01\x4f\x8b\x30\xa3\xfd\xf5\xac\x56\xeb\xaf\
+        \xa2\x19\xeb\x89\x1d\x4b\xbb\xab\x7e\x1d\xf3\xb2\x72\x05\xb4\x59\
+    ";
+    test_marsupilami_fourteen("", "", 64, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_long() {
+    let expected = b"\
+        \xc0\x93\x22\xde\x15\x13\xd0\xcd\x60\x47\x28\xf3\x6d\x11\xad\xff\
+        \x58\xb9\x3f\x77\x63\x81\x09\x5a\x07\x19\x21\xea\xfb\x30\xe1\xe3\
+        \xa9\x87\xea\x7b\xb4\x13\xf5\xf2\xba\xe0\x40\x89\xa8\x66\xb4\x79\
+        \xd2\x89\x3a\x11\xb3\x29\xa1\x65\x7f\xbe\x3c\xaa\xb8\x07\x78\x68\
+    ";
+    test_marsupilami_fourteen("", "", 10064, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_with_message() {
+    let expected = b"\
+        \xcc\x05\xeb\xc9\x28\x15\x6c\x7a\x03\x54\x00\x85\x35\x5c\x47\xc6\
+        \xae\xa1\xd0\x7d\xc8\x11\xcd\xde\xd0\xe4\xc3\x67\xf8\xd9\x93\x68\
+        \xa5\x31\x82\x5d\x99\x64\x13\xa9\xbc\x0e\x1e\x57\x2f\xf5\xdf\x4f\
+        \x98\xca\x65\xf4\xfb\x49\x00\xee\x23\x55\xf5\x95\x99\xe2\xf6\x48\
+    ";
+    test_marsupilami_fourteen("", pattern(1), 64, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_with_message2() {
+    let expected = b"\
+        \xaa\x76\x4f\xd8\xb3\x8f\x19\x97\x6a\x30\x5c\xb0\x07\xf1\x93\x84\
+        \xb2\x10\xa5\xc7\xb0\xfc\x44\x99\xd6\xf8\x3c\x62\x27\xbf\xf8\x50\
+        \x27\x0b\x88\x0c\xff\x3f\x17\x32\x5b\x84\x3e\x97\x2a\xe0\xb9\x9a\
+        \x25\xfa\x0e\x00\x50\xcc\x74\x8f\x37\xc4\xcf\xc2\x59\x2f\xd1\x72\
+    ";
+    test_marsupilami_fourteen("", pattern(17), 64, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_with_message3() {
+    let expected = b"\
+        \x35\xaf\x0a\x5f\xc6\xc4\xd1\x11\xfb\xc6\x8f\x87\x9d\x05\x50\x6a\
+        \xaf\xd3\x00\xb5\xab\x13\x69\x86\xd7\xae\xd8\xa9\xf1\xbe\x33\x1e\
+        \x86\x64\x38\x18\x64\x67\x2e\x81\xba\x32\xd8\x28\xb2\xc0\x51\x92\
+        \xa5\x88\x68\x46\xf6\xc7\x57\x0e\x7e\xba\xeb\x97\xb5\x9b\xd7\x3e\
+    ";
+    test_marsupilami_fourteen("", pattern(83521), 64, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_with_custom_string() {
+    let expected = b"\
+        \xe6\xc2\x3c\xee\xab\x20\x89\xd1\x4d\xc3\xb0\x88\xfd\xfe\x6d\x44\
+        \x18\xbf\x8a\x6f\x33\x0f\xb3\xed\xcc\x30\x0c\xd8\x1e\x1b\xef\x2f\
+        \x0c\xab\x47\x9b\x19\x6e\x53\xbe\x8f\xa2\x87\x85\x4d\x48\x4f\xdf\
+        \xd0\x84\xaf\x3a\xe1\xff\xac\x9b\x04\xc2\xe9\xea\x2b\x5a\x1c\x7b\
+    ";
+    test_marsupilami_fourteen(pattern(1), "", 64, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_with_custom_string_and_message() {
+    let expected = b"\
+        \x2b\xab\x75\xb3\x1b\x8c\x30\x49\xab\xeb\x76\x74\x77\x47\x71\xb6\
+        \x4f\x59\x22\x5b\xe2\x0e\x93\x0e\xbd\xbf\x8e\x37\xc2\x4f\xad\x69\
+        \xbe\xf4\x7a\x41\x2d\xb6\x20\x94\xd5\xcc\x95\xde\x8e\x4f\xc2\xc0\
+        \xae\x65\xfd\x0f\x4d\x03\xbb\x56\xe6\x29\x2b\xe0\x84\xfc\xc8\xe3\
+    ";
+    test_marsupilami_fourteen(pattern(41), [0xff], 64, expected);
+}
+
+#[test]
+fn marsupilami_fourteen_with_custom_string_and_message2() {
+    let expected = b"\
+        \x61\x58\x3c\xdf\xaa\x64\xab\x60\xe7\x7b\x8c\x8b\xdd\x0a\xd0\x88\
+        \xf9\xd7\x60\xb2\x94\x4f\x7d\x64\xc5\xdd\x81\xce\x7e\x92\xd9\x6b\
+        \xff\x67\x84\x3a\x1e\xed\x51\xf3\x01\xdb\x51\xff\x54\xfd\xcd\x44\
+        \x62\xfd\x05\x14\x25\xd4\xc2\xed\xba\x74\xac\x2b\x15\x32\xec\x14\
+    ";
+    test_marsupilami_fourteen(
+        pattern(68921),
+        [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        64,
+        expected,
+    );
+}
+
+#[test]
+fn marsupilami_fourteen_xof() {
+    let mut expected = [0u8; 128];
+    let mut marsupilami = MarsupilamiFourteen::new(b"");
+    marsupilami.update(&pattern(8193));
+    marsupilami.clone().finalize(&mut expected);
+
+    let mut output = [0u8; 128];
+    let mut xof = marsupilami.into_xof();
+    xof.squeeze(&mut output[..33]);
+    xof.squeeze(&mut output[33..]);
+    assert_eq!(&expected[..], &output[..]);
+}
@@ -0,0 +1,186 @@
+use core::slice::SliceIndex;
+use tiny_keccak::{
+    keccakf, keccakf_masked, mask, unmask, Hasher, IntoXof, Kmac, MaskedKmac, MaskedShake, Rng,
+    Shake, Xof,
+};
+
+/// xorshift64, good enough to test that the masking does not change the results.
+struct XorShift(u64);
+
+impl Rng for XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn input(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + 3) as u8).collect()
+}
+
+// `each_ref` and `each_mut` of arrays are newer than the minimum supported Rust version
+fn slices<T, R, const D: usize>(shares: &[T; D], range: R) -> [&[u8]; D]
+where
+    T: AsRef<[u8]>,
+    R: SliceIndex<[u8], Output = [u8]> + Clone,
+{
+    core::array::from_fn(|i| &shares[i].as_ref()[range.clone()])
+}
+
+fn slices_mut<T, R, const D: usize>(shares: &mut [T; D], range: R) -> [&mut [u8]; D]
+where
+    T: AsMut<[u8]>,
+    R: SliceIndex<[u8], Output = [u8]> + Clone,
+{
+    let mut shares = shares.iter_mut();
+    core::array::from_fn(|_| &mut shares.next().unwrap().as_mut()[range.clone()])
+}
+
+fn check_keccakf_masked<const D: usize>() {
+    let mut rng = XorShift(0x2545f4914f6cdd1d);
+    let mut shares = [[0u64; 25]; D];
+    for share in shares.iter_mut() {
+        for lane in share.iter_mut() {
+            *lane = rng.next_u64();
+        }
+    }
+
+    let mut expected = [0u64; 25];
+    for share in shares.iter() {
+        for (lane, share) in expected.iter_mut().zip(share.iter()) {
+            *lane ^= share;
+        }
+    }
+    keccakf(&mut expected);
+
+    keccakf_masked(&mut shares, &mut rng);
+    let mut state = [0u64; 25];
+    for share in shares.iter() {
+        for (lane, share) in state.iter_mut().zip(share.iter()) {
+            *lane ^= share;
+        }
+    }
+    assert_eq!(expected, state);
+    assert_ne!(expected, shares[0]);
+}
+
+#[test]
+fn keccakf_masked_matches_keccakf() {
+    check_keccakf_masked::<2>();
+    check_keccakf_masked::<3>();
+    check_keccakf_masked::<4>();
+}
+
+#[test]
+#[should_panic(expected = "masking requires at least 2 shares")]
+fn keccakf_masked_single_share() {
+    keccakf_masked(&mut [[0u64; 25]; 1], &mut XorShift(1));
+}
+
+#[test]
+fn mask_unmask() {
+    let value = input(37);
+    let (mut a, mut b, mut c) = ([0u8; 37], [0u8; 37], [0u8; 37]);
+    mask(&value, [&mut a, &mut b, &mut c], &mut XorShift(7));
+    assert_ne!(&value[..], &a[..]);
+
+    let mut output = [0u8; 37];
+    unmask([&a, &b, &c], &mut output);
+    assert_eq!(&value[..], &output[..]);
+}
+
+fn check_shake<const D: usize>(len: usize) {
+    let value = input(len);
+    let mut rng = XorShift(0x9e3779b97f4a7c15);
+    let mut shares = [(); D].map(|_| vec![0u8; len]);
+    mask(&value, slices_mut(&mut shares, ..), &mut rng);
+
+    let mut expected = [0u8; 400];
+    let mut shake = Shake::v256();
+    shake.update(&value);
+    shake.finalize(&mut expected);
+
+    let mut outputs = [(); D].map(|_| [0u8; 400]);
+    let mut masked = MaskedShake::<_, D>::v256(rng);
+    let (first, second) = (len / 3, len - len / 3);
+    masked.update(slices(&shares, ..first));
+    masked.update(slices(&shares, first..second));
+    masked.update(slices(&shares, second..));
+    masked.squeeze(slices_mut(&mut outputs, ..100));
+    masked.finalize(slices_mut(&mut outputs, 100..));
+
+    let mut output = [0u8; 400];
+    unmask(slices(&outputs, ..), &mut output);
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+fn masked_shake_matches_shake() {
+    for &len in &[0, 1, 135, 136, 137, 500] {
+        check_shake::<2>(len);
+        check_shake::<3>(len);
+    }
+}
+
+fn masked_kmac<const D: usize>(key: &[u8], rng: &mut XorShift) -> MaskedKmac<XorShift, D> {
+    let mut key_shares = [(); D].map(|_| vec![0u8; key.len()]);
+    mask(key, slices_mut(&mut key_shares, ..), rng);
+    MaskedKmac::v128(
+        slices(&key_shares, ..),
+        b"My Tagged Application",
+        XorShift(rng.next_u64()),
+    )
+}
+
+fn check_kmac<const D: usize>(len: usize) {
+    let key = input(32);
+    let value = input(len);
+    let mut rng = XorShift(0x853c49e6748fea9b);
+    let mut shares = [(); D].map(|_| vec![0u8; len]);
+    mask(&value, slices_mut(&mut shares, ..), &mut rng);
+
+    let mut expected = [0u8; 32];
+    let mut kmac = Kmac::v128(&key, b"My Tagged Application");
+    kmac.update(&value);
+    kmac.finalize(&mut expected);
+
+    let mut outputs = [[0u8; 32]; D];
+    let mut masked = masked_kmac::<D>(&key, &mut rng);
+    masked.update(slices(&shares, ..));
+    masked.finalize(slices_mut(&mut outputs, ..));
+
+    let mut output = [0u8; 32];
+    unmask(slices(&outputs, ..), &mut output);
+    assert_eq!(expected, output);
+
+    let mut expected = [0u8; 300];
+    let mut kmac = Kmac::v128(&key, b"My Tagged Application");
+    kmac.update(&value);
+    kmac.into_xof().squeeze(&mut expected);
+
+    let mut outputs = [[0u8; 300]; D];
+    let mut masked = masked_kmac::<D>(&key, &mut rng);
+    masked.update(slices(&shares, ..));
+    masked.into_xof().squeeze(slices_mut(&mut outputs, ..));
+
+    let mut output = [0u8; 300];
+    unmask(slices(&outputs, ..), &mut output);
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+fn masked_kmac_matches_kmac() {
+    for &len in &[0, 4, 168, 200] {
+        check_kmac::<2>(len);
+        check_kmac::<3>(len);
+    }
+}
+
+#[test]
+#[should_panic(expected = "all shares must be equally long")]
+fn masked_shake_unequal_shares() {
+    let mut shake = MaskedShake::<_, 2>::v128(XorShift(1));
+    shake.update([&[0u8; 4], &[0u8; 5]]);
+}
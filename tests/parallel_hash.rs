@@ -121,3 +121,64 @@ fn test_parallel_hash256_three() {
     phash.finalize(&mut output);
     assert_eq!(expected as &[u8], &output as &[u8]);
 }
+
+#[test]
+fn test_parallel_hash_batched_blocks_match_incremental() {
+    let input: Vec<u8> = (0..15 * 200 + 7).map(|j| (j % 251) as u8).collect();
+    for bits in &[128, 256] {
+        let new = |block_size| match bits {
+            128 => ParallelHash::v128(b"custom", block_size),
+            _ => ParallelHash::v256(b"custom", block_size),
+        };
+
+        let mut expected = [0u8; 64];
+        let mut hasher = new(200);
+        for chunk in input.chunks(150) {
+            hasher.update(chunk);
+        }
+        hasher.finalize(&mut expected);
+
+        let mut output = [0u8; 64];
+        let mut hasher = new(200);
+        hasher.update(&input);
+        hasher.finalize(&mut output);
+        assert_eq!(&expected[..], &output[..]);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_hash_update_parallel_matches_sequential() {
+    // more blocks than are hashed in one parallel window
+    let input: Vec<u8> = (0..9000 * 8 + 3).map(|j| (j % 251) as u8).collect();
+    for bits in &[128, 256] {
+        let new = |block_size| match bits {
+            128 => ParallelHash::v128(b"custom", block_size),
+            _ => ParallelHash::v256(b"custom", block_size),
+        };
+
+        for block_size in &[8, 200] {
+            let mut expected = [0u8; 64];
+            let mut hasher = new(*block_size);
+            hasher.update(&input);
+            hasher.finalize(&mut expected);
+
+            let mut output = [0u8; 64];
+            let mut hasher = new(*block_size);
+            hasher.update_parallel(&input);
+            hasher.finalize(&mut output);
+            assert_eq!(&expected[..], &output[..]);
+
+            // unfinished blocks carry over between sequential and parallel updates
+            let mut output = [0u8; 64];
+            let mut hasher = new(*block_size);
+            hasher.update(&input[..5]);
+            hasher.update_parallel(&input[5..1001]);
+            hasher.update_parallel(&input[1001..1002]);
+            hasher.update(&input[1002..3000]);
+            hasher.update_parallel(&input[3000..]);
+            hasher.finalize(&mut output);
+            assert_eq!(&expected[..], &output[..]);
+        }
+    }
+}
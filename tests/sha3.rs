@@ -92,3 +92,89 @@ fn long_string_sha3_512_parts() {
     sha3.finalize(&mut output);
     assert_eq!(expected as &[u8], &output as &[u8]);
 }
+
+#[test]
+fn sha3_batch_matches_one_by_one() {
+    // equal lengths run in lock-step, mixed lengths split the batch into smaller groups
+    let lengths = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 135, 136, 137, 272, 300, 300, 300, 300, 1000, 1,
+    ];
+    let inputs: Vec<Vec<u8>> = lengths
+        .iter()
+        .enumerate()
+        .map(|(i, len)| (0..*len).map(|j| (i * 7 + j) as u8).collect())
+        .collect();
+
+    let mut outputs = vec![[0u8; 32]; inputs.len()];
+    Sha3::v256_batch(&inputs, &mut outputs);
+    for (input, output) in inputs.iter().zip(&outputs) {
+        let mut expected = [0u8; 32];
+        let mut sha3 = Sha3::v256();
+        sha3.update(input);
+        sha3.finalize(&mut expected);
+        assert_eq!(&expected, output);
+    }
+
+    let mut outputs = vec![[0u8; 64]; inputs.len()];
+    Sha3::v512_batch(&inputs, &mut outputs);
+    for (input, output) in inputs.iter().zip(&outputs) {
+        let mut expected = [0u8; 64];
+        let mut sha3 = Sha3::v512();
+        sha3.update(input);
+        sha3.finalize(&mut expected);
+        assert_eq!(&expected[..], &output[..]);
+    }
+}
+
+#[test]
+#[should_panic]
+fn sha3_batch_output_count_mismatch() {
+    let inputs: [&[u8]; 2] = [b"hello", b"world"];
+    let mut outputs = [[0u8; 32]; 1];
+    Sha3::v256_batch(&inputs, &mut outputs);
+}
+
+#[cfg(feature = "fault_detection")]
+#[test]
+fn sha3_try_finalize_matches_finalize() {
+    let mut expected = [0u8; 64];
+    let mut sha3 = Sha3::v512();
+    sha3.update(b"hello world");
+    sha3.finalize(&mut expected);
+
+    let mut output = [0u8; 64];
+    let mut sha3 = Sha3::v512();
+    sha3.update(b"hello world");
+    assert_eq!(sha3.try_finalize(&mut output), Ok(()));
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+fn sha3_steps_match_update_and_finalize() {
+    let input: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+    for &len in &[0, 135, 136, 137, 1000] {
+        let mut expected = [0u8; 32];
+        let mut sha3 = Sha3::v256();
+        sha3.update(&input[..len]);
+        sha3.finalize(&mut expected);
+
+        for &rounds in &[1, 5, 24, 100] {
+            let mut output = [0u8; 32];
+            let mut sha3 = Sha3::v256();
+            while sha3.update_step(&input[..len], rounds).is_pending() {}
+            let mut pending = sha3.finalize_step(&mut output, rounds);
+            while let Some(sha3) = pending {
+                pending = sha3.finalize_step(&mut output, rounds);
+            }
+            assert_eq!(expected, output);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "a stepped operation is still pending")]
+fn sha3_update_while_step_pending() {
+    let mut sha3 = Sha3::v256();
+    assert!(sha3.update_step(&[0u8; 200], 1).is_pending());
+    sha3.update(b"hello");
+}
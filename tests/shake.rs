@@ -35,3 +35,50 @@ fn shake_xof_two() {
 
     assert_eq!(expected, &output);
 }
+
+#[test]
+fn shake_batch_matches_one_by_one() {
+    let inputs: Vec<Vec<u8>> = (0..9).map(|i| vec![0xa3; 200 * i]).collect();
+
+    // outputs longer than the rate are squeezed in several blocks
+    let mut outputs = vec![[0u8; 400]; inputs.len()];
+    Shake::v128_batch(&inputs, &mut outputs);
+    for (input, output) in inputs.iter().zip(&outputs) {
+        let mut expected = [0u8; 400];
+        let mut shake = Shake::v128();
+        shake.update(input);
+        shake.finalize(&mut expected);
+        assert_eq!(&expected[..], &output[..]);
+    }
+
+    let mut outputs = vec![[0u8; 17]; inputs.len()];
+    Shake::v256_batch(&inputs, &mut outputs);
+    for (input, output) in inputs.iter().zip(&outputs) {
+        let mut expected = [0u8; 17];
+        let mut shake = Shake::v256();
+        shake.update(input);
+        shake.finalize(&mut expected);
+        assert_eq!(&expected, output);
+    }
+}
+
+#[test]
+fn shake_steps_match_update_and_squeeze() {
+    let input: Vec<u8> = (0..500).map(|i| (i * 7) as u8).collect();
+    let mut expected = [0u8; 400];
+    let mut shake = Shake::v128();
+    shake.update(&input[..100]);
+    shake.squeeze(&mut expected[..200]);
+    shake.update(&input[100..]);
+    shake.squeeze(&mut expected[200..]);
+
+    for &rounds in &[1, 7, 24] {
+        let mut output = [0u8; 400];
+        let mut shake = Shake::v128();
+        while shake.update_step(&input[..100], rounds).is_pending() {}
+        while shake.squeeze_step(&mut output[..200], rounds).is_pending() {}
+        while shake.update_step(&input[100..], rounds).is_pending() {}
+        while shake.squeeze_step(&mut output[200..], rounds).is_pending() {}
+        assert_eq!(&expected[..], &output[..]);
+    }
+}
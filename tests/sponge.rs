@@ -0,0 +1,100 @@
+use tiny_keccak::{Hasher, KeccakF, Permutation, Sha3, Shake, Sponge, Xof};
+
+#[test]
+fn sponge_sha3_256() {
+    let mut sponge = Sponge::<KeccakF>::new(136, 0x06);
+    let mut sha3 = Sha3::v256();
+    let mut expected = [0u8; 32];
+    let mut output = [0u8; 32];
+
+    sponge.absorb(b"hello");
+    sponge.absorb(b" world");
+    sponge.squeeze(&mut output);
+    sha3.update(b"hello world");
+    sha3.finalize(&mut expected);
+
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn sponge_shake_128_half_duplex() {
+    let mut sponge = Sponge::<KeccakF>::new(168, 0x1f);
+    let mut shake = Shake::v128();
+    let mut expected = [0u8; 300];
+    let mut output = [0u8; 300];
+
+    sponge.absorb(&[0xa3; 200]);
+    sponge.pad();
+    sponge.pad();
+    sponge.squeeze(&mut output[..100]);
+    sponge.absorb(&[0xa3; 200]);
+    sponge.squeeze(&mut output[100..]);
+    shake.update(&[0xa3; 200]);
+    shake.squeeze(&mut expected[..100]);
+    shake.update(&[0xa3; 200]);
+    shake.squeeze(&mut expected[100..]);
+
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+fn sponge_rate_and_capacity() {
+    let sponge = Sponge::<KeccakF>::new(136, 0x06);
+    assert_eq!(sponge.rate(), 136);
+    assert_eq!(sponge.capacity(), 64);
+}
+
+#[test]
+fn sponge_reset() {
+    let mut sponge = Sponge::<KeccakF>::new(136, 0x06);
+    let mut expected = [0u8; 32];
+    let mut output = [0u8; 32];
+
+    sponge.clone().squeeze(&mut expected);
+    sponge.absorb(b"hello world");
+    sponge.reset();
+    sponge.squeeze(&mut output);
+
+    assert_eq!(expected, output);
+}
+
+struct Counting;
+
+static mut CALLS: usize = 0;
+
+impl Permutation for Counting {
+    type Lane = u64;
+
+    fn execute(state: &mut [u64; 25]) {
+        unsafe {
+            CALLS += 1;
+        }
+        state[0] = state[0].wrapping_add(1);
+    }
+}
+
+#[test]
+fn sponge_custom_permutation() {
+    let mut sponge = Sponge::<Counting>::new(8, 0x01);
+    let mut output = [0u8; 16];
+
+    sponge.absorb(&[0u8; 20]);
+    sponge.squeeze(&mut output);
+
+    // two absorbed blocks, one after padding and one after each squeezed block
+    assert_eq!(unsafe { CALLS }, 5);
+    assert_eq!(&output[..8], &[0x03, 0, 0, 0, 0x01, 0, 0, 0x80]);
+    assert_eq!(&output[8..], &[0x04, 0, 0, 0, 0x01, 0, 0, 0x80]);
+}
+
+#[test]
+#[should_panic]
+fn sponge_rate_too_big() {
+    Sponge::<KeccakF>::new(201, 0x06);
+}
+
+#[test]
+#[should_panic(expected = "suffix cannot be equal 0")]
+fn sponge_zero_suffix() {
+    Sponge::<KeccakF>::new(136, 0x00);
+}
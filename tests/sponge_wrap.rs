@@ -0,0 +1,243 @@
+use tiny_keccak::{InvalidTag, SpongeWrap};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn key() -> Vec<u8> {
+    (0..16).collect()
+}
+
+fn nonce() -> [u8; 16] {
+    let mut nonce = [0u8; 16];
+    for (i, byte) in nonce.iter_mut().enumerate() {
+        *byte = 100 + i as u8;
+    }
+    nonce
+}
+
+#[test]
+fn sponge_wrap_session() {
+    let mut sponge_wrap = SpongeWrap::new(&key(), &nonce());
+
+    let mut data = pattern(200);
+    let mut tag = [0u8; 16];
+    let expected_ciphertext = b"\
+        \x27\x7a\xb0\x12\x42\xe5\x6d\x97\xa9\x44\x0c\x60\xac\xcc\x2d\xad\
+        \x94\x29\x62\x15\x1e\xc3\x3b\x08\x86\xb9\xc4\xca\x76\xd3\x87\x92\
+        \x25\x26\xd0\x42\xa2\x46\x9e\x4a\x39\x79\x9c\xff\x94\x2b\x24\x19\
+        \x2c\xda\xb3\x94\xd4\x85\xae\x1e\xa0\x0d\xb0\x77\x85\xef\x00\xe0\
+        \x99\x0f\xf2\x6e\x67\x9a\xcc\xb9\x7f\x89\xcb\x97\x3d\xc7\xe0\x29\
+        \x91\x8a\x66\xea\x82\x57\xe7\x78\x56\xd5\x29\x52\x06\xbe\x44\x52\
+        \xb1\x55\xbe\x73\x95\x73\x85\x1b\x2d\x3d\x70\x02\xf8\x4c\xf7\x02\
+        \x95\x2c\xb1\xe9\x06\xb0\x17\x7d\xd0\x5b\xde\xd4\xce\xdd\xd7\x57\
+        \x9d\xca\x92\x9a\xc5\x6b\x9f\x24\x2f\x54\xdf\xf1\x1e\xf9\x87\x0f\
+        \x8a\x16\xb0\x2b\x5d\xbd\x8e\x39\xe7\x7e\x73\x3f\xd4\x93\x28\xcf\
+        \xda\x57\x50\xbf\x54\x44\xbc\xe6\x5d\x22\x88\xc0\x06\xf1\x0f\xec\
+        \xc7\xe9\xbc\xc1\xde\xc9\x98\xd7\x21\x69\x74\x79\x89\x57\xac\xaf\
+        \x52\xfe\x2f\xe5\x1a\x5b\x00\x9d\
+    ";
+    let expected_tag = b"\
+        \x6b\x1a\x8d\xac\x4f\x03\x70\x8a\xba\x46\x4e\x4e\xb5\xdc\xd9\x99\
+    ";
+    sponge_wrap.wrap(b"header", &mut data, &mut tag);
+    assert_eq!(&expected_ciphertext[..], &data[..]);
+    assert_eq!(expected_tag, &tag);
+
+    let mut tag = [0u8; 16];
+    let expected_tag = b"\
+        \xca\x38\xa5\x8e\x8a\xf0\xad\x11\x57\x26\xa3\x84\x78\x34\xde\x07\
+    ";
+    sponge_wrap.wrap(b"", &mut [], &mut tag);
+    assert_eq!(expected_tag, &tag);
+
+    let mut data = pattern(3);
+    let mut tag = [0u8; 200];
+    let expected_ciphertext = b"\x63\xfc\x77";
+    let expected_tag = b"\
+        \xb3\xce\xb6\x52\x30\x78\x63\x41\xd5\xd8\x40\xa7\x31\x07\x5a\xcc\
+        \xd9\x61\xc0\xb9\x59\xaf\x54\xa6\xb8\x6f\xd2\xf9\x98\x40\x56\x67\
+        \xfd\x2b\x47\x77\x8f\xe9\x7b\x45\x0a\x2d\xfa\x1a\x5e\xfc\xf3\x8a\
+        \xf2\x50\x19\x7b\x34\x16\xb5\xfd\xf5\x8e\xd2\x21\x39\xe3\x7a\xea\
+        \x08\x0a\x9c\xdb\x30\xb0\xb3\x59\x6b\x7b\x8f\x5c\x19\xf4\x8a\x46\
+        \x64\x6a\x5d\x8d\x01\x93\x7c\xf7\xfd\xac\xa4\xf3\x10\x67\xcc\x67\
+        \x08\xed\x3c\xda\x57\x08\xf0\x3f\x96\x12\xa3\x01\x73\xba\x4c\xbf\
+        \x75\x63\x5b\x7b\x6f\xd4\x67\xdb\x50\x3d\xcd\xb5\xd8\x13\x98\xc6\
+        \x17\x7d\x55\xdf\x0c\xc7\x44\x94\x0e\x82\x52\xbe\x08\x9c\x9a\x91\
+        \x6a\x62\x23\x63\x4f\xf5\x49\x3e\x33\x57\xbc\x0f\x05\xb9\x13\x7c\
+        \x77\xe2\x75\x98\x35\x3f\x2a\x7f\xa8\xdf\x62\x88\xee\xd3\x5b\x28\
+        \x22\xa7\x6e\x7b\x97\x93\x91\xfd\x14\xf6\xbf\xd0\xb9\xbf\x2f\x92\
+        \x74\xa4\xb9\x37\x4b\xe0\xb9\xaf\
+    ";
+    sponge_wrap.wrap(&pattern(400), &mut data, &mut tag);
+    assert_eq!(&expected_ciphertext[..], &data[..]);
+    assert_eq!(&expected_tag[..], &tag[..]);
+}
+
+#[test]
+fn sponge_wrap_long_key() {
+    let mut data = *b"hello world";
+    let mut tag = [0u8; 16];
+    let expected_ciphertext = b"\x83\xc2\x5c\xa5\x3f\x71\xbc\x39\x23\x1a\x90";
+    let expected_tag = b"\
+        \x75\x07\x57\xe3\xca\x76\x6b\x30\x24\x99\x41\x7c\x89\x8b\x3f\x98\
+    ";
+    SpongeWrap::new(&pattern(300), &nonce()).wrap(b"", &mut data, &mut tag);
+    assert_eq!(expected_ciphertext, &data);
+    assert_eq!(expected_tag, &tag);
+}
+
+#[test]
+fn sponge_wrap_session_unwrap() {
+    let mut sender = SpongeWrap::new(&key(), &nonce());
+    let mut receiver = SpongeWrap::new(&key(), &nonce());
+
+    for len in &[0, 1, 166, 167, 168, 500] {
+        let plaintext = pattern(*len);
+        let mut data = plaintext.clone();
+        let mut tag = [0u8; 32];
+        sender.wrap(b"ad", &mut data, &mut tag);
+        receiver.unwrap(b"ad", &mut data, &tag).unwrap();
+        assert_eq!(plaintext, data);
+    }
+}
+
+#[test]
+fn sponge_wrap_seal_open() {
+    let plaintext = pattern(300);
+    let mut sealed = vec![0u8; 300 + SpongeWrap::TAG_LEN];
+    let mut opened = vec![0u8; 300];
+
+    SpongeWrap::seal(&key(), &nonce(), b"ad", &plaintext, &mut sealed);
+    SpongeWrap::open(&key(), &nonce(), b"ad", &sealed, &mut opened).unwrap();
+    assert_eq!(plaintext, opened);
+}
+
+#[test]
+fn sponge_wrap_open_invalid() {
+    let plaintext = pattern(300);
+    let mut sealed = vec![0u8; 300 + SpongeWrap::TAG_LEN];
+    let mut opened = vec![0u8; 300];
+    SpongeWrap::seal(&key(), &nonce(), b"ad", &plaintext, &mut sealed);
+
+    let result = SpongeWrap::open(&key(), &nonce(), b"da", &sealed, &mut opened);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(opened, vec![0u8; 300]);
+
+    sealed[10] ^= 1;
+    let result = SpongeWrap::open(&key(), &nonce(), b"ad", &sealed, &mut opened);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(opened, vec![0u8; 300]);
+
+    sealed[10] ^= 1;
+    sealed[300] ^= 1;
+    let result = SpongeWrap::open(&key(), &nonce(), b"ad", &sealed, &mut opened);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(opened, vec![0u8; 300]);
+}
+
+#[test]
+fn sponge_wrap_open_truncated() {
+    let mut opened = [0xaa; 4];
+    let result = SpongeWrap::open(&key(), &nonce(), b"ad", &[0u8; 15], &mut opened);
+    assert_eq!(result, Err(InvalidTag));
+    assert_eq!(opened, [0u8; 4]);
+    assert_eq!(
+        SpongeWrap::open(&key(), &nonce(), b"ad", &[], &mut []),
+        Err(InvalidTag)
+    );
+}
+
+#[test]
+#[should_panic(expected = "output must fit the plaintext")]
+fn sponge_wrap_open_output_mismatch() {
+    let mut opened = [0u8; 4];
+    let _ = SpongeWrap::open(&key(), &nonce(), b"ad", &[0u8; 21], &mut opened);
+}
+
+fn wrap_chunked(
+    sponge_wrap: &mut SpongeWrap,
+    ad: &[u8],
+    data: &mut [u8],
+    tag: &mut [u8],
+    chunk: usize,
+) {
+    for piece in ad.chunks(chunk) {
+        sponge_wrap.absorb_ad(piece);
+    }
+    for piece in data.chunks_mut(chunk) {
+        sponge_wrap.encrypt_chunk(piece);
+    }
+    sponge_wrap.finalize_tag(tag);
+}
+
+#[test]
+fn sponge_wrap_chunked_matches_one_shot() {
+    for chunk in &[1, 7, 165, 166, 167, 400] {
+        let mut sender = SpongeWrap::new(&key(), &nonce());
+        let mut expected = SpongeWrap::new(&key(), &nonce());
+        let mut receiver = SpongeWrap::new(&key(), &nonce());
+
+        for (ad_len, len) in &[(6, 200), (0, 0), (332, 166), (400, 3), (1, 500)] {
+            let ad = pattern(*ad_len);
+            let mut data = pattern(*len);
+            let mut expected_data = data.clone();
+            let mut tag = [0u8; 40];
+            let mut expected_tag = [0u8; 40];
+            wrap_chunked(&mut sender, &ad, &mut data, &mut tag, *chunk);
+            expected.wrap(&ad, &mut expected_data, &mut expected_tag);
+            assert_eq!(expected_data, data);
+            assert_eq!(expected_tag, tag);
+
+            for piece in ad.chunks(*chunk) {
+                receiver.absorb_ad(piece);
+            }
+            for piece in data.chunks_mut(*chunk) {
+                receiver.decrypt_chunk(piece);
+            }
+            receiver.verify_tag(&tag).unwrap();
+            assert_eq!(pattern(*len), data);
+        }
+    }
+}
+
+#[test]
+fn sponge_wrap_chunked_known_answer() {
+    // the start of the first message of `sponge_wrap_session`
+    let expected_ciphertext = b"\
+        \x27\x7a\xb0\x12\x42\xe5\x6d\x97\xa9\x44\x0c\x60\xac\xcc\x2d\xad\
+    ";
+    let expected_tag = b"\
+        \x6b\x1a\x8d\xac\x4f\x03\x70\x8a\xba\x46\x4e\x4e\xb5\xdc\xd9\x99\
+    ";
+    for chunk in &[1, 5, 166, 167] {
+        let mut data = pattern(200);
+        let mut tag = [0u8; 16];
+        let mut sponge_wrap = SpongeWrap::new(&key(), &nonce());
+        wrap_chunked(&mut sponge_wrap, b"header", &mut data, &mut tag, *chunk);
+        assert_eq!(&expected_ciphertext[..], &data[..16]);
+        assert_eq!(expected_tag, &tag);
+    }
+}
+
+#[test]
+fn sponge_wrap_verify_tag_invalid() {
+    let mut data = pattern(200);
+    let mut tag = [0u8; 16];
+    SpongeWrap::new(&key(), &nonce()).wrap(b"ad", &mut data, &mut tag);
+
+    tag[15] ^= 1;
+    let mut receiver = SpongeWrap::new(&key(), &nonce());
+    receiver.absorb_ad(b"ad");
+    receiver.decrypt_chunk(&mut data);
+    assert_eq!(receiver.verify_tag(&tag), Err(InvalidTag));
+}
+
+#[test]
+#[should_panic]
+fn sponge_wrap_ad_after_data() {
+    let mut sponge_wrap = SpongeWrap::new(&key(), &nonce());
+    sponge_wrap.absorb_ad(b"ad");
+    sponge_wrap.encrypt_chunk(&mut [0u8; 10]);
+    sponge_wrap.absorb_ad(b"more ad");
+}
@@ -0,0 +1,176 @@
+use tiny_keccak::{InvalidTag, Strobe};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn transcript(mut strobe: Strobe, ciphertext: &[u8], mac: &[u8], plaintext: &[u8], prf: &[u8]) {
+    strobe.meta_ad(b"key", false);
+    strobe.key(&pattern(32), false);
+    strobe.ad(&pattern(100), false);
+    strobe.ad(&pattern(300), true);
+    strobe.meta_send_clr(b"header", false);
+    strobe.send_clr(b"cleartext", false);
+
+    let mut data = pattern(200);
+    strobe.send_enc(&mut data, false);
+    assert_eq!(ciphertext, &data[..]);
+
+    let mut output = [0u8; 16];
+    strobe.send_mac(&mut output, false);
+    assert_eq!(mac, &output[..]);
+
+    strobe.recv_clr(b"reply", false);
+    let mut data = pattern(10);
+    strobe.recv_enc(&mut data, false);
+    assert_eq!(plaintext, &data[..]);
+
+    strobe.ratchet(32, false);
+    let mut output = [0u8; 64];
+    strobe.prf(&mut output, false);
+    assert_eq!(prf, &output[..]);
+}
+
+#[test]
+fn strobe_merlin() {
+    // the `simple` test vector of the Merlin transcript construction
+    let mut strobe = Strobe::v128(b"Merlin v1.0");
+    for (label, message) in &[
+        (&b"dom-sep"[..], &b"test protocol"[..]),
+        (b"some label", b"some data"),
+    ] {
+        strobe.meta_ad(label, false);
+        strobe.meta_ad(&(message.len() as u32).to_le_bytes(), true);
+        strobe.ad(message, false);
+    }
+    strobe.meta_ad(b"challenge", false);
+    strobe.meta_ad(&32u32.to_le_bytes(), true);
+
+    let mut output = [0u8; 32];
+    let expected = b"\
+        \xd5\xa2\x19\x72\xd0\xd5\xfe\x32\x0c\x0d\x26\x3f\xac\x7f\xff\xb8\
+        \x14\x5a\xa6\x40\xaf\x6e\x9b\xca\x17\x7c\x03\xc7\xef\xcf\x06\x15\
+    ";
+    strobe.prf(&mut output, false);
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn strobe128_transcript() {
+    let ciphertext = b"\
+        \xe2\xe5\xa2\xc5\x81\x51\x6d\x7b\xda\x3f\x72\x89\x4d\x1d\xbf\x8e\
+        \x47\x91\xa6\xb1\x38\x23\x7c\x80\xdf\x2d\x2c\xbb\x6d\x65\x9d\xfa\
+        \xa1\x0f\x8a\x05\xe7\x4c\xe7\x3e\x91\x86\xc2\xff\x87\xd8\x06\xdc\
+        \x21\x5a\xb4\x18\x52\x79\x16\x3e\xdc\x85\x36\xbd\x6c\x5f\xc9\x03\
+        \x75\x67\x9a\x96\xa6\xfd\xfe\x72\x8a\xa6\x82\x6c\x3a\x85\xbf\x8c\
+        \xcd\x39\x35\xca\xc4\x31\xea\xa0\xa6\x0d\xff\xf9\xd1\x49\x98\x33\
+        \xf8\x5d\x52\x36\x22\xf7\x56\x7e\x76\xa3\x7c\x7f\x7d\x67\x98\xeb\
+        \x48\x75\xe3\x6d\xd4\xb6\x43\x24\xd3\x60\x80\xb7\x84\xcc\x80\xd0\
+        \x40\xa6\xe2\x31\xaf\x07\xb1\x42\x9e\x96\x46\x5a\x20\xa3\xd4\xa0\
+        \xd3\xe1\x17\x66\x2f\x0e\x9c\x74\xc3\x92\x52\xdc\x75\x73\x0b\xac\
+        \xb7\x03\x6d\x44\xbc\xd2\xe8\xa3\x24\xed\x62\x2b\x85\x63\x8e\xee\
+        \x8b\xa6\x60\xe6\xbe\x92\x2f\x30\xf4\x8b\x15\xc6\x87\xca\xdc\x0f\
+        \x84\x83\x5f\x84\xdd\xad\xdb\x15\
+    ";
+    let mac = b"\x95\x4f\xf3\x81\xbf\xa6\x9e\x82\xb3\x57\xb1\x78\x6c\x45\xc6\xc2";
+    let plaintext = b"\xe9\xed\x59\xb8\xae\x32\xde\x4a\x0c\x3e";
+    let prf = b"\
+        \xf8\xaa\xea\xb9\x6f\x2d\x7b\x7c\xac\xc5\x74\x0e\x9e\x46\x0d\x29\
+        \x12\xd5\xb3\xb8\x16\x9b\x7e\xc4\x39\x51\xbe\xcc\x56\x49\x5e\x96\
+        \x29\x83\xa5\xbe\x67\x2a\x45\xa0\xef\x62\xbe\xc5\xa9\x09\x0b\x98\
+        \xa7\x03\xb0\x60\x0b\xf9\xf8\xb9\x90\xfe\x3f\x30\xd9\xd5\xee\x1c\
+    ";
+    transcript(
+        Strobe::v128(b"tiny-keccak test"),
+        ciphertext,
+        mac,
+        plaintext,
+        prf,
+    );
+}
+
+#[test]
+fn strobe256_transcript() {
+    let ciphertext = b"\
+        \xbb\x02\x71\x34\x94\x5a\xdc\x9d\xb2\xf2\x80\x73\xb8\x37\x12\x88\
+        \xa6\xed\x22\x5b\x33\xe5\x7d\x54\x15\x48\xca\x8b\xd7\x0c\x4b\x75\
+        \xfe\x30\xf1\x53\x71\xb0\x97\x0b\x11\xb7\x83\x3a\x76\x40\x35\x7a\
+        \x75\x42\x57\xfd\x4e\x1f\x30\xab\x42\xf7\x24\x1d\xbc\x2d\xda\xa0\
+        \x52\xdc\xfe\x3b\x8e\xcd\x72\x15\x91\xf8\x9d\x11\x7a\xba\x10\xad\
+        \xcf\x53\xcc\xdc\xf6\xf9\x4f\x4f\xf4\xd2\x61\x4a\x5c\xde\x57\xf3\
+        \x7e\x8d\x53\x3e\x7f\x92\x38\xac\x46\x56\x3e\x4a\x8f\x1c\x37\x52\
+        \x7d\xd8\x56\x66\x93\xca\xe3\x8d\x3d\x5d\xa5\x7f\xca\x4c\x5b\xd7\
+        \x6c\x90\x1b\xcd\x45\x02\xaf\x01\xf8\x0f\xe5\xdb\x28\x68\x14\x08\
+        \xd7\xb8\x57\xdf\xc6\x7e\x49\xf8\xc3\x22\xa0\x05\xd6\x0c\xa3\xe8\
+        \xde\xf3\x8e\x2a\x64\x38\x7a\x88\xf3\xb0\x1c\x93\x0b\xa4\x90\xbd\
+        \x7a\xd9\x81\xf8\x93\x24\xab\x79\x7f\x18\xe4\xe9\xd8\x2f\xd9\x75\
+        \x2c\x3d\x3b\xfb\xb6\x94\x44\xe4\
+    ";
+    let mac = b"\x8e\x54\x3d\xc3\xc7\x2b\xa9\x2d\x38\xdb\xb0\x1f\x1d\xdd\x1e\x64";
+    let plaintext = b"\xfb\x2e\xf5\xd2\x3d\x78\x01\x79\x1b\x01";
+    let prf = b"\
+        \xdd\xca\x8f\x28\xef\xb7\xc2\xa8\xea\x80\x01\x62\x02\x97\xae\xa5\
+        \xa7\x73\x6a\x64\x81\x81\xf3\xac\x31\x94\xc8\x30\x4e\x43\x9b\xd5\
+        \x60\x9d\xed\x6d\xad\xf2\x1e\x79\xaf\xc4\x5b\x20\x9f\xac\xa4\xa0\
+        \x19\xf0\xe6\x97\xb0\xa7\x8b\x87\x98\xb9\xc2\x14\x4a\x9a\x3b\x31\
+    ";
+    transcript(
+        Strobe::v256(b"tiny-keccak test"),
+        ciphertext,
+        mac,
+        plaintext,
+        prf,
+    );
+}
+
+#[test]
+fn strobe_session() {
+    let mut alice = Strobe::v128(b"tiny-keccak test");
+    let mut bob = Strobe::v128(b"tiny-keccak test");
+    alice.key(b"shared secret", false);
+    bob.key(b"shared secret", false);
+
+    for len in &[0, 1, 165, 166, 167, 500] {
+        let plaintext = pattern(*len);
+        let mut data = plaintext.clone();
+        let mut mac = [0u8; 16];
+        alice.send_enc(&mut data, false);
+        alice.send_mac(&mut mac, false);
+        bob.recv_enc(&mut data, false);
+        bob.recv_mac(&mac).unwrap();
+        assert_eq!(plaintext, data);
+
+        bob.send_clr(&plaintext, false);
+        bob.send_mac(&mut mac, false);
+        alice.recv_clr(&plaintext, false);
+        alice.recv_mac(&mac).unwrap();
+    }
+
+    let mut alice_prf = [0u8; 32];
+    let mut bob_prf = [0u8; 32];
+    alice.prf(&mut alice_prf, false);
+    bob.prf(&mut bob_prf, false);
+    assert_eq!(alice_prf, bob_prf);
+}
+
+#[test]
+fn strobe_recv_mac_invalid() {
+    let mut alice = Strobe::v256(b"tiny-keccak test");
+    let mut bob = Strobe::v256(b"tiny-keccak test");
+    alice.key(b"shared secret", false);
+    bob.key(b"shared secret", false);
+
+    let mut mac = [0u8; 32];
+    alice.send_clr(b"hello", false);
+    alice.send_mac(&mut mac, false);
+    bob.recv_clr(b"hellO", false);
+    assert_eq!(bob.recv_mac(&mac), Err(InvalidTag));
+}
+
+#[test]
+#[should_panic(expected = "cannot continue an operation with different flags")]
+fn strobe_more_different_flags() {
+    let mut strobe = Strobe::v128(b"tiny-keccak test");
+    strobe.ad(b"hello", false);
+    strobe.meta_ad(b"world", true);
+}
@@ -0,0 +1,127 @@
+use tiny_keccak::{Hasher, IntoXof, Transcript, TupleHash, Xof};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn transcript(
+    mut transcript: Transcript,
+    expected: &[u8],
+    expected_parent: &[u8],
+    expected_fork: &[u8],
+) {
+    transcript.append_message(b"some label", b"some data");
+    transcript.append_u64(b"round", 7);
+    let mut challenge = [0u8; 32];
+    transcript.challenge_bytes(b"challenge", &mut challenge);
+    assert_eq!(expected, &challenge[..]);
+
+    let mut fork = transcript.fork(b"prover");
+    let mut challenge = [0u8; 64];
+    transcript.challenge_bytes(b"challenge", &mut challenge);
+    assert_eq!(expected_parent, &challenge[..]);
+
+    fork.append_message(b"some label", &pattern(300));
+    fork.challenge_bytes(b"challenge", &mut challenge);
+    assert_eq!(expected_fork, &challenge[..]);
+}
+
+#[test]
+fn transcript128() {
+    let expected = b"\
+        \xdb\xd6\xbd\x52\xd3\xfd\xd2\xf9\x79\x80\xb8\xb9\x5d\xd0\xe7\x40\
+        \xb6\x62\xc9\x0b\x08\xe2\xbb\x5e\xd7\x3a\x05\x4d\x92\xe3\x7e\x2c\
+    ";
+    let expected_parent = b"\
+        \xe0\x8e\xf1\x21\x5a\x12\x7e\xd7\x5f\x35\xaa\xf6\xef\x7a\xbe\x8d\
+        \x73\x37\xf2\xfe\x81\x58\xd1\xc1\x00\xed\x90\xd1\xd3\x5e\x6b\x8b\
+        \x5d\x2e\xd9\xc6\x5e\xf6\x5d\xcd\x86\xd5\x84\x1e\xaa\xbb\x16\x12\
+        \x6e\x3d\x3b\x41\xa6\x12\xad\x06\x3c\xab\xc2\x24\x56\x88\x62\x2c\
+    ";
+    let expected_fork = b"\
+        \x76\xb7\x98\xa4\x50\xcf\x7d\xd8\x37\xc6\xea\xbe\x79\xa8\x7c\xb3\
+        \xce\xaf\x7f\x44\xd3\x8c\x08\xff\x93\xc6\x47\x99\xee\x17\xe0\xb5\
+        \xe8\xe0\x28\x91\x0c\x3d\xdb\xa5\x96\x3e\xee\xb1\xa8\x0a\xe7\xce\
+        \x3c\x17\xf2\xc8\x21\x9e\xc9\x79\x0c\x9f\x16\xe5\x10\xe3\x47\xa2\
+    ";
+    transcript(
+        Transcript::v128(b"test protocol"),
+        expected,
+        expected_parent,
+        expected_fork,
+    );
+}
+
+#[test]
+fn transcript256() {
+    let expected = b"\
+        \x90\xe4\x3f\x7d\xbd\xa8\x8d\xc0\x4b\x78\xc2\x30\x61\xb3\xe8\xa8\
+        \x9e\xe8\xfe\x59\xa4\xce\x40\xe6\x58\x17\x55\xf8\x45\x60\x8c\x93\
+    ";
+    let expected_parent = b"\
+        \x64\x31\x5d\x47\x1d\x2d\xe8\xc4\x77\x95\x31\xbd\x14\x30\x37\x75\
+        \xc7\x39\xb2\x59\x86\x1b\x0d\xec\x9c\xe0\xcf\x1e\xab\x96\xcf\xc3\
+        \x0c\x79\x73\x11\xba\xef\xf4\xfe\xca\x52\x21\xbe\x0a\x56\xcb\xae\
+        \x20\x31\xe2\xbc\x76\xaf\xc4\xf8\x2c\xa5\xc9\x51\x84\x6c\x49\x66\
+    ";
+    let expected_fork = b"\
+        \x61\x95\xed\xb2\x78\x82\xaa\xdf\xc8\x72\xa9\x60\x6b\x8d\x25\x7e\
+        \xf4\xf8\x15\x87\xcc\x5e\xbc\xa8\x2f\xc8\xe7\xb6\x96\xb6\x03\x9a\
+        \x88\x9c\x50\x0b\xc5\xf7\x0e\x91\xcd\x77\xfb\xc6\x53\xad\x99\x4d\
+        \x10\xa3\x7d\x14\x30\x05\x64\x1a\xc4\xbd\x81\xf1\x25\x1c\x50\xa4\
+    ";
+    transcript(
+        Transcript::v256(b"test protocol"),
+        expected,
+        expected_parent,
+        expected_fork,
+    );
+}
+
+#[test]
+fn transcript_is_tuple_hash_xof() {
+    let mut transcript = Transcript::v128(b"test protocol");
+    transcript.append_message(b"some label", b"some data");
+    let mut challenge = [0u8; 32];
+    transcript.challenge_bytes(b"challenge", &mut challenge);
+
+    let mut hasher = TupleHash::v128(b"test protocol");
+    for element in &[
+        &b"\x01"[..],
+        b"some label",
+        b"some data",
+        b"\x02",
+        b"challenge",
+        &32u64.to_le_bytes(),
+    ] {
+        hasher.update(element);
+    }
+    let mut expected = [0u8; 32];
+    hasher.into_xof().squeeze(&mut expected);
+    assert_eq!(expected, challenge);
+}
+
+#[test]
+fn transcript_unambiguous() {
+    let mut challenges = Vec::new();
+    for (label, message) in &[(&b"ab"[..], &b"c"[..]), (b"a", b"bc"), (b"abc", b"")] {
+        let mut transcript = Transcript::v128(b"test protocol");
+        transcript.append_message(label, message);
+        let mut challenge = [0u8; 32];
+        transcript.challenge_bytes(b"challenge", &mut challenge);
+        challenges.push(challenge);
+    }
+    assert_ne!(challenges[0], challenges[1]);
+    assert_ne!(challenges[0], challenges[2]);
+    assert_ne!(challenges[1], challenges[2]);
+}
+
+#[test]
+fn transcript_consecutive_challenges() {
+    let mut transcript = Transcript::v128(b"test protocol");
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    transcript.challenge_bytes(b"challenge", &mut first);
+    transcript.challenge_bytes(b"challenge", &mut second);
+    assert_ne!(first, second);
+}
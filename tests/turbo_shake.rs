@@ -0,0 +1,153 @@
+use tiny_keccak::{Hasher, TurboShake, Xof};
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+fn test_turbo_shake<A: AsRef<[u8]>>(
+    bits: usize,
+    message: A,
+    domain: u8,
+    output_len: usize,
+    expected: &[u8],
+) {
+    let mut turbo_shake = match bits {
+        128 => TurboShake::v128(domain),
+        256 => TurboShake::v256(domain),
+        _ => unreachable!(),
+    };
+    turbo_shake.update(message.as_ref());
+    let mut res = vec![0; output_len];
+    turbo_shake.finalize(&mut res);
+    assert_eq!(&res[output_len - expected.len()..], expected);
+}
+
+#[test]
+fn empty_turbo_shake128() {
+    let expected = b"\
+        \x1e\x41\x5f\x1c\x59\x83\xaf\xf2\x16\x92\x17\x27\x7d\x17\xbb\x53\
+        \x8c\xd9\x45\xa3\x97\xdd\xec\x54\x1f\x1c\xe4\x1a\xf2\xc1\xb7\x4c\
+        \x3e\x8c\xca\xe2\xa4\xda\xe5\x6c\x84\xa0\x4c\x23\x85\xc0\x3c\x15\
+        \xe8\x19\x3b\xdf\x58\x73\x73\x63\x32\x16\x91\xc0\x54\x62\xc8\xdf\
+    ";
+    test_turbo_shake(128, "", 0x1f, 64, expected);
+}
+
+#[test]
+fn turbo_shake128_long() {
+    let expected = b"\
+        \xa3\xb9\xb0\x38\x59\x00\xce\x76\x1f\x22\xae\xd5\x48\xe7\x54\xda\
+        \x10\xa5\x24\x2d\x62\xe8\xc6\x58\xe3\xf3\xa9\x23\xa7\x55\x56\x07\
+    ";
+    test_turbo_shake(128, "", 0x1f, 10032, expected);
+}
+
+#[test]
+fn turbo_shake128_with_message() {
+    let expected = b"\
+        \x55\xce\xdd\x6f\x60\xaf\x7b\xb2\x9a\x40\x42\xae\x83\x2e\xf3\xf5\
+        \x8d\xb7\x29\x9f\x89\x3e\xbb\x92\x47\x24\x7d\x85\x69\x58\xda\xa9\
+    ";
+    test_turbo_shake(128, pattern(1), 0x1f, 32, expected);
+}
+
+#[test]
+fn turbo_shake128_with_message2() {
+    let expected = b"\
+        \x9c\x97\xd0\x36\xa3\xba\xc8\x19\xdb\x70\xed\xe0\xca\x55\x4e\xc6\
+        \xe4\xc2\xa1\xa4\xff\xbf\xd9\xec\x26\x9c\xa6\xa1\x11\x16\x12\x33\
+    ";
+    test_turbo_shake(128, pattern(17), 0x1f, 32, expected);
+}
+
+#[test]
+fn turbo_shake128_with_message3() {
+    let expected = b"\
+        \xb9\x7a\x90\x6f\xbf\x83\xef\x7c\x81\x25\x17\xab\xf3\xb2\xd0\xae\
+        \xa0\xc4\xf6\x03\x18\xce\x11\xcf\x10\x39\x25\x12\x7f\x59\xee\xcd\
+    ";
+    test_turbo_shake(128, pattern(1419857), 0x1f, 32, expected);
+}
+
+#[test]
+fn turbo_shake128_with_domain() {
+    let expected = b"\
+        \xbf\x32\x3f\x94\x04\x94\xe8\x8e\xe1\xc5\x40\xfe\x66\x0b\xe8\xa0\
+        \xc9\x3f\x43\xd1\x5e\xc0\x06\x99\x84\x62\xfa\x99\x4e\xed\x5d\xab\
+    ";
+    test_turbo_shake(128, [0xff, 0xff, 0xff], 0x01, 32, expected);
+}
+
+#[test]
+fn turbo_shake128_with_domain2() {
+    let expected = b"\
+        \x16\x27\x4c\xc6\x56\xd4\x4c\xef\xd4\x22\x39\x5d\x0f\x90\x53\xbd\
+        \xa6\xd2\x8e\x12\x2a\xba\x15\xc7\x65\xe5\xad\x0e\x6e\xaf\x26\xf9\
+    ";
+    test_turbo_shake(128, [0xff, 0xff, 0xff], 0x7f, 32, expected);
+}
+
+#[test]
+fn empty_turbo_shake256() {
+    let expected = b"\
+        \x36\x7a\x32\x9d\xaf\xea\x87\x1c\x78\x02\xec\x67\xf9\x05\xae\x13\
+        \xc5\x76\x95\xdc\x2c\x66\x63\xc6\x10\x35\xf5\x9a\x18\xf8\xe7\xdb\
+        \x11\xed\xc0\xe1\x2e\x91\xea\x60\xeb\x6b\x32\xdf\x06\xdd\x7f\x00\
+        \x2f\xba\xfa\xbb\x6e\x13\xec\x1c\xc2\x0d\x99\x55\x47\x60\x0d\xb0\
+    ";
+    test_turbo_shake(256, "", 0x1f, 64, expected);
+}
+
+#[test]
+fn turbo_shake256_long() {
+    let expected = b"\
+        \xab\xef\xa1\x16\x30\xc6\x61\x26\x92\x49\x74\x26\x85\xec\x08\x2f\
+        \x20\x72\x65\xdc\xcf\x2f\x43\x53\x4e\x9c\x61\xba\x0c\x9d\x1d\x75\
+    ";
+    test_turbo_shake(256, "", 0x1f, 10032, expected);
+}
+
+#[test]
+fn turbo_shake256_with_message() {
+    let expected = b"\
+        \x3e\x17\x12\xf9\x28\xf8\xea\xf1\x05\x46\x32\xb2\xaa\x0a\x24\x6e\
+        \xd8\xb0\xc3\x78\x72\x8f\x60\xbc\x97\x04\x10\x15\x5c\x28\x82\x0e\
+    ";
+    test_turbo_shake(256, pattern(1), 0x1f, 32, expected);
+}
+
+#[test]
+fn turbo_shake256_with_message2() {
+    let expected = b"\
+        \xad\xd5\x3b\x06\x54\x3e\x58\x4b\x58\x23\xf6\x26\x99\x6a\xee\x50\
+        \xfe\x45\xed\x15\xf2\x02\x43\xa7\x16\x54\x85\xac\xb4\xaa\x76\xb4\
+    ";
+    test_turbo_shake(256, pattern(1419857), 0x1f, 32, expected);
+}
+
+#[test]
+fn turbo_shake256_with_domain() {
+    let expected = b"\
+        \xd2\x1c\x6f\xbb\xf5\x87\xfa\x22\x82\xf2\x9a\xea\x62\x01\x75\xfb\
+        \x02\x57\x41\x3a\xf7\x8a\x0b\x1b\x2a\x87\x41\x9c\xe0\x31\xd9\x33\
+    ";
+    test_turbo_shake(256, [0xff, 0xff, 0xff], 0x01, 32, expected);
+}
+
+#[test]
+fn turbo_shake_xof() {
+    let mut turbo_shake = TurboShake::v128(TurboShake::DEFAULT_DOMAIN);
+    let mut expected = [0u8; 64];
+    turbo_shake.clone().finalize(&mut expected);
+
+    let mut output = [0u8; 64];
+    turbo_shake.squeeze(&mut output[..17]);
+    turbo_shake.squeeze(&mut output[17..]);
+    assert_eq!(&expected[..], &output[..]);
+}
+
+#[test]
+#[should_panic]
+fn turbo_shake_invalid_domain() {
+    TurboShake::v128(0x80);
+}